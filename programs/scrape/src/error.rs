@@ -16,4 +16,213 @@ pub enum ErrorCode {
 
     #[msg("Bandwidth usage exceeds the paid amount.")]
     BandwidthExceeded,
-}
\ No newline at end of file
+
+    #[msg("Task deadline must be in the future.")]
+    InvalidDeadline,
+
+    #[msg("Task deadline has not passed yet.")]
+    DeadlineNotReached,
+
+    #[msg("Task still has escrowed funds locked.")]
+    EscrowLocked,
+
+    #[msg("Task deadline has passed.")]
+    TaskExpired,
+
+    #[msg("Assignment has not timed out yet.")]
+    AssignmentNotStalled,
+
+    #[msg("Task has no submitted result to review.")]
+    ResultNotSubmitted,
+
+    #[msg("Review window is still open.")]
+    ReviewWindowOpen,
+
+    #[msg("Review window has closed.")]
+    ReviewWindowClosed,
+
+    #[msg("Dispute is not open.")]
+    DisputeNotOpen,
+
+    #[msg("Signer is not a registered arbiter.")]
+    NotAnArbiter,
+
+    #[msg("Arbiter has already voted on this dispute.")]
+    AlreadyVoted,
+
+    #[msg("Not enough arbiter votes to settle the dispute.")]
+    QuorumNotReached,
+
+    #[msg("Provider share must be between 0 and 10000 basis points.")]
+    InvalidShare,
+
+    #[msg("Arbiter quorum must be between 1 and the maximum number of arbiters.")]
+    InvalidQuorum,
+
+    #[msg("Arbiter registry is full.")]
+    ArbiterRegistryFull,
+
+    #[msg("Arbiter is already registered.")]
+    ArbiterAlreadyRegistered,
+
+    #[msg("Arbiter is not registered.")]
+    ArbiterNotFound,
+
+    #[msg("Stake is below the required minimum.")]
+    InsufficientStake,
+
+    #[msg("No unstake request is pending.")]
+    UnstakeNotRequested,
+
+    #[msg("Unstake cooldown has not elapsed.")]
+    UnstakeCooldownActive,
+
+    #[msg("Reputation half-life must be positive.")]
+    InvalidHalfLife,
+
+    #[msg("Signer is not the config admin.")]
+    UnauthorizedAdmin,
+
+    #[msg("Config parameters are invalid.")]
+    InvalidConfig,
+
+    #[msg("Fee receiver does not match the config.")]
+    InvalidFeeReceiver,
+
+    #[msg("Token accounts are required for $SCRAPE payments.")]
+    MissingPaymentAccount,
+
+    #[msg("Listing price must be greater than zero.")]
+    InvalidPrice,
+
+    #[msg("Dataset listing is not active.")]
+    ListingInactive,
+
+    #[msg("Dataset size must be greater than zero.")]
+    InvalidDatasetSize,
+
+    #[msg("Dataset record count must be greater than zero.")]
+    InvalidRecordCount,
+
+    #[msg("Dataset content hash must be set.")]
+    InvalidContentHash,
+
+    #[msg("Dataset URI is malformed or too long for its storage kind.")]
+    InvalidStorageUri,
+
+    #[msg("Signer is not allowed to assign this task.")]
+    UnauthorizedAssigner,
+
+    #[msg("Session key must differ from the owner and expire in the future.")]
+    InvalidSessionKey,
+
+    #[msg("Task already has a selected provider.")]
+    ProviderAlreadySelected,
+
+    #[msg("Provider node was not selected for this task.")]
+    ProviderNotSelected,
+
+    #[msg("Candidate nodes must match the node registry.")]
+    IncompleteCandidateSet,
+
+    #[msg("Randomness source is missing or not the configured one.")]
+    InvalidRandomness,
+
+    #[msg("Registry page index is out of range, or free slots must be used first.")]
+    InvalidRegistryPage,
+
+    #[msg("Registry page has no free slot.")]
+    RegistryPageFull,
+
+    #[msg("Provider node is not listed in the node registry.")]
+    NodeNotRegistered,
+
+    #[msg("Provider node is already listed in the node registry.")]
+    NodeAlreadyRegistered,
+
+    #[msg("Provider node still has assigned tasks in flight.")]
+    NodeHasActiveTasks,
+
+    #[msg("Stake must be fully withdrawn first.")]
+    StakeNotWithdrawn,
+
+    #[msg("Capability profile is malformed.")]
+    InvalidCapabilities,
+
+    #[msg("Task requirements are malformed.")]
+    InvalidRequirements,
+
+    #[msg("Provider node does not meet the task requirements or is at capacity.")]
+    RequirementsNotMet,
+
+    #[msg("Connection commitment must be set.")]
+    InvalidConnectionCommitment,
+
+    #[msg("Sealed connection info is empty or too long.")]
+    InvalidSealedConnection,
+
+    #[msg("Endpoint node has no encryption key.")]
+    MissingEncryptionKey,
+
+    #[msg("Account is not a ProviderNode owned by the signer.")]
+    InvalidMigration,
+
+    #[msg("Task reward does not pay for any bandwidth.")]
+    InsufficientReward,
+    #[msg("Bonus funding must be greater than zero and target an epoch that has not ended.")]
+    InvalidBonusFunding,
+    #[msg("Bonus epoch has not ended yet.")]
+    BonusEpochNotEnded,
+    #[msg("Bonus for this epoch has already been claimed.")]
+    BonusAlreadyClaimed,
+    #[msg("Escrow pool balance is insufficient for this payout.")]
+    InsufficientEscrowBalance,
+    #[msg("Bonus epoch is still open for claims or has already been swept.")]
+    BonusEpochNotSweepable,
+    #[msg("Bonus epoch has been swept.")]
+    BonusEpochSwept,
+    #[msg("Bonus pool balance is insufficient for this payout.")]
+    InsufficientBonusBalance,
+    #[msg("Treasury balance is insufficient for this payout.")]
+    InsufficientTreasuryBalance,
+    #[msg("Vault is paused after a failed reconciliation.")]
+    VaultPaused,
+    #[msg("Vault balance does not cover its recorded liabilities.")]
+    VaultInsolvent,
+    #[msg("Signer is not authorized to manage the treasury.")]
+    UnauthorizedTreasury,
+    #[msg("Multisig needs 1 to 16 distinct signers and a threshold between 1 and the signer count.")]
+    InvalidMultisig,
+    #[msg("Invalid treasury action.")]
+    InvalidTreasuryAction,
+    #[msg("Signer has already approved this proposal.")]
+    AlreadyApproved,
+    #[msg("Proposal has already been executed.")]
+    ProposalExecuted,
+    #[msg("Proposal was made for a previous signer set.")]
+    StaleProposal,
+    #[msg("Proposal does not have enough approvals.")]
+    ThresholdNotMet,
+    #[msg("Withdrawal destination does not match the proposal.")]
+    InvalidWithdrawalDestination,
+    #[msg("Mint is not approved for task rewards.")]
+    MintNotApproved,
+    #[msg("The $SCRAPE vault accounts are required to slash stake.")]
+    MissingStakeVault,
+    #[msg("Signer is not the program upgrade authority.")]
+    UnauthorizedDeployer,
+    #[msg("The committed slot has not passed yet.")]
+    RandomnessNotReady,
+    #[msg("Account is not a NodeRegistry in the legacy layout.")]
+    InvalidRegistryMigration,
+    #[msg("Task result has not been rejected.")]
+    ResultNotRejected,
+    #[msg("Listing price is above the buyer's maximum.")]
+    PriceAboveMax,
+    #[msg("Selected provider node is still eligible for the task.")]
+    ProviderStillSelectable,
+    #[msg("Account is not a TokenVault in the legacy layout, or not its token account.")]
+    InvalidVaultMigration,
+    #[msg("Arithmetic overflow.")]
+    MathOverflow,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::reputation::ReputationEvent;
+
+#[event]
+pub struct ReputationChanged {
+    pub node: Pubkey,           // ProviderNode owner
+    pub event: ReputationEvent, // Program event that caused the change
+    pub reputation: u64,        // Score after decay and the event's weight
+    pub timestamp: i64,         // Unix timestamp of the change
+}
+
+#[event]
+pub struct ProviderSelected {
+    pub task: Pubkey,           // Task the provider was drawn for
+    pub node: Pubkey,           // Selected ProviderNode owner
+    pub seed: [u8; 32],         // Seed the draw was made with, for off-chain verification
+    pub slot: u64,              // Slot of the draw
+}
+
+#[event]
+pub struct ClientPeriodRolled {
+    pub client: Pubkey,         // Client owner
+    pub period_start: i64,      // Start of the allowance period that closed
+    pub allowance_used: u64,    // Free allowance consumed in that period (MB)
+    pub next_period_start: i64, // Start of the new period
+}
+
+#[event]
+pub struct VaultReconciled {
+    pub balance: u64,           // $SCRAPE held by the vault's token account
+    pub escrow: u64,            // Escrow pool
+    pub bonus_pool: u64,        // Unclaimed bonus emissions
+    pub treasury: u64,          // Protocol-owned fees and slashed stake
+    pub shortfall: u64,         // Liabilities not backed by the balance
+    pub surplus: u64,           // Balance not owed to any pool
+    pub bandwidth_paid_mb: u64, // Bandwidth paid for (MB)
+    pub bandwidth_used_mb: u64, // Bandwidth used (MB)
+    pub paused: bool,           // Circuit breaker state after the check
+    pub slot: u64,              // Slot of the reconciliation
+}
@@ -0,0 +1,42 @@
+use crate::error::ErrorCode;
+use crate::state::arbiter_registry::ArbiterRegistry;
+use anchor_lang::prelude::*;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct InitializeArbiterRegistryContext<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ArbiterRegistry::SIZE,
+        seeds = [ArbiterRegistry::PREFIX.as_bytes()],
+        bump
+    )]
+    pub arbiter_registry: Box<Account<'info, ArbiterRegistry>>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Admin-only, so that the registry cannot be claimed by whoever initializes it first.
+pub fn init(ctx: Context<InitializeArbiterRegistryContext>, authority: Pubkey, quorum: u8) -> Result<()> {
+    require!(
+        quorum > 0 && quorum as usize <= ArbiterRegistry::MAX_ARBITERS,
+        ErrorCode::InvalidQuorum
+    );
+
+    let arbiter_registry = &mut ctx.accounts.arbiter_registry;
+    arbiter_registry.bump = ctx.bumps.arbiter_registry;
+    arbiter_registry.authority = authority;
+    arbiter_registry.quorum = quorum;
+    arbiter_registry.arbiters = Vec::new();
+
+    msg!("Arbiter registry initialized. Authority: {}, Quorum: {}", authority, quorum);
+    Ok(())
+}
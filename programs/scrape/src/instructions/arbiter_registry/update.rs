@@ -0,0 +1,60 @@
+use crate::error::ErrorCode;
+use crate::state::arbiter_registry::ArbiterRegistry;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateArbiterRegistryContext<'info> {
+    #[account(
+        mut,
+        seeds = [ArbiterRegistry::PREFIX.as_bytes()],
+        bump = arbiter_registry.bump,
+        has_one = authority @ ErrorCode::UnauthorizedNode
+    )]
+    pub arbiter_registry: Box<Account<'info, ArbiterRegistry>>,
+    pub authority: Signer<'info>,
+}
+
+#[inline(never)]
+pub fn add_arbiter(ctx: Context<UpdateArbiterRegistryContext>, arbiter: Pubkey) -> Result<()> {
+    let arbiter_registry = &mut ctx.accounts.arbiter_registry;
+
+    require!(!arbiter_registry.is_arbiter(&arbiter), ErrorCode::ArbiterAlreadyRegistered);
+    require!(
+        arbiter_registry.arbiters.len() < ArbiterRegistry::MAX_ARBITERS,
+        ErrorCode::ArbiterRegistryFull
+    );
+
+    arbiter_registry.arbiters.push(arbiter);
+
+    msg!("Arbiter added: {}. Total arbiters: {}", arbiter, arbiter_registry.arbiters.len());
+    Ok(())
+}
+
+#[inline(never)]
+pub fn remove_arbiter(ctx: Context<UpdateArbiterRegistryContext>, arbiter: Pubkey) -> Result<()> {
+    let arbiter_registry = &mut ctx.accounts.arbiter_registry;
+
+    let index = arbiter_registry
+        .arbiters
+        .iter()
+        .position(|key| *key == arbiter)
+        .ok_or(ErrorCode::ArbiterNotFound)?;
+    arbiter_registry.arbiters.swap_remove(index);
+
+    msg!("Arbiter removed: {}. Total arbiters: {}", arbiter, arbiter_registry.arbiters.len());
+    Ok(())
+}
+
+#[inline(never)]
+pub fn set_quorum(ctx: Context<UpdateArbiterRegistryContext>, quorum: u8) -> Result<()> {
+    require!(
+        quorum > 0 && quorum as usize <= ArbiterRegistry::MAX_ARBITERS,
+        ErrorCode::InvalidQuorum
+    );
+
+    let arbiter_registry = &mut ctx.accounts.arbiter_registry;
+    arbiter_registry.quorum = quorum;
+
+    msg!("Arbiter quorum set to {}", quorum);
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use crate::error::ErrorCode;
+use crate::state::bonus::{BonusClaim, BonusEpoch};
+use crate::state::config::Config;
+use crate::state::provider_node::ProviderNode;
+use crate::state::reputation::ReputationConfig;
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct ClaimBonusContext<'info> {
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), node.owner.as_ref()],
+        bump
+    )]
+    pub node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        mut,
+        seeds = [BonusEpoch::PREFIX.as_bytes(), epoch.to_le_bytes().as_ref()],
+        bump = bonus_epoch.bump
+    )]
+    pub bonus_epoch: Box<Account<'info, BonusEpoch>>,
+    #[account(
+        mut,
+        seeds = [BonusClaim::PREFIX.as_bytes(), node.owner.as_ref(), epoch.to_le_bytes().as_ref()],
+        bump = bonus_claim.bump
+    )]
+    pub bonus_claim: Box<Account<'info, BonusClaim>>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), config.params.scrape_mint.as_ref()],
+        bump = token_vault.bump,
+        constraint = !token_vault.paused @ ErrorCode::VaultPaused
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = config.params.scrape_mint)]
+    pub scrape_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = node_token_account.mint == config.params.scrape_mint,
+        constraint = node_token_account.owner == node.owner,
+    )]
+    pub node_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [ReputationConfig::PREFIX.as_bytes()],
+        bump = reputation_config.bump
+    )]
+    pub reputation_config: Box<Account<'info, ReputationConfig>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pays the node its share of an ended epoch's pool, proportional to the accepted work credited to
+/// it during that epoch. Claims close once the epoch is swept.
+#[inline(never)]
+pub fn claim(ctx: Context<ClaimBonusContext>, epoch: u64) -> Result<()> {
+    let node = &mut ctx.accounts.node;
+    let bonus_epoch = &mut ctx.accounts.bonus_epoch;
+    let bonus_claim = &mut ctx.accounts.bonus_claim;
+    let token_vault = &mut ctx.accounts.token_vault;
+    let params = &ctx.accounts.config.params;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(node.is_operator(&ctx.accounts.signer.key(), now), ErrorCode::UnauthorizedNode);
+    require!(
+        epoch < BonusEpoch::at(now, params.bonus_epoch_length),
+        ErrorCode::BonusEpochNotEnded
+    );
+    require!(!bonus_claim.claimed, ErrorCode::BonusAlreadyClaimed);
+    require!(!bonus_epoch.swept, ErrorCode::BonusEpochSwept);
+    require!(
+        node.current_reputation(now, ctx.accounts.reputation_config.weights.half_life) >= params.reputation_threshold,
+        ErrorCode::InsufficientReputation
+    );
+
+    let bonus = bonus_epoch.share(bonus_claim.contribution)?;
+    require!(
+        bonus <= bonus_epoch.funded.saturating_sub(bonus_epoch.claimed),
+        ErrorCode::InsufficientBonusBalance
+    );
+    if bonus > 0 {
+        token_vault.pay_bonus(bonus)?;
+
+        let mint = token_vault.mint;
+        let bump = [token_vault.bump];
+        let seeds: &[&[u8]] = &[TokenVault::PREFIX.as_bytes(), mint.as_ref(), &bump];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault_token_account,
+            &mut ctx.accounts.node_token_account,
+            &ctx.accounts.scrape_mint,
+            token_vault.to_account_info(),
+            &[seeds],
+            bonus,
+        )?;
+    }
+
+    bonus_claim.claimed = true;
+    bonus_epoch.claimed = bonus_epoch.claimed.checked_add(bonus).ok_or(ErrorCode::MathOverflow)?;
+    node.bonus_rewards = node.bonus_rewards.checked_add(bonus).ok_or(ErrorCode::MathOverflow)?;
+    node.last_bonus_claim = now;
+
+    msg!(
+        "Bonus for epoch {} claimed by node: {}. Contribution: {} of {}. Paid: {} $SCRAPE",
+        epoch,
+        node.owner,
+        bonus_claim.contribution,
+        bonus_epoch.total_contribution,
+        bonus
+    );
+    Ok(())
+}
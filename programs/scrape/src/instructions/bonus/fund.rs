@@ -0,0 +1,80 @@
+use crate::error::ErrorCode;
+use crate::state::bonus::BonusEpoch;
+use crate::state::config::Config;
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct FundBonusEpochContext<'info> {
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = BonusEpoch::SIZE,
+        seeds = [BonusEpoch::PREFIX.as_bytes(), epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bonus_epoch: Box<Account<'info, BonusEpoch>>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), config.params.scrape_mint.as_ref()],
+        bump = token_vault.bump,
+        constraint = !token_vault.paused @ ErrorCode::VaultPaused
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = config.params.scrape_mint)]
+    pub scrape_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = funder_token_account.mint == config.params.scrape_mint,
+        constraint = funder_token_account.owner == signer.key(),
+    )]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: deposits `amount` $SCRAPE into the emission pool of `epoch`. Funding closes when
+/// the epoch ends so that the pool is fixed before the first claim.
+#[inline(never)]
+pub fn fund(ctx: Context<FundBonusEpochContext>, epoch: u64, amount: u64) -> Result<()> {
+    let current = BonusEpoch::current(ctx.accounts.config.params.bonus_epoch_length)?;
+    require!(amount > 0 && epoch >= current, ErrorCode::InvalidBonusFunding);
+
+    let funded = transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.funder_token_account,
+        &mut ctx.accounts.vault_token_account,
+        &ctx.accounts.scrape_mint,
+        ctx.accounts.signer.to_account_info(),
+        &[],
+        amount,
+    )?;
+
+    ctx.accounts.token_vault.fund_bonus(funded)?;
+
+    let bonus_epoch = &mut ctx.accounts.bonus_epoch;
+    bonus_epoch.bump = ctx.bumps.bonus_epoch;
+    bonus_epoch.epoch = epoch;
+    bonus_epoch.funded = bonus_epoch.funded.checked_add(funded).ok_or(ErrorCode::MathOverflow)?;
+
+    msg!(
+        "Bonus epoch {} funded with {} $SCRAPE by {}. Pool: {}",
+        epoch,
+        funded,
+        ctx.accounts.signer.key(),
+        bonus_epoch.funded
+    );
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod claim;
+pub mod fund;
+pub mod sweep;
+
+pub use claim::*;
+pub use fund::*;
+pub use sweep::*;
@@ -0,0 +1,60 @@
+use crate::error::ErrorCode;
+use crate::state::bonus::BonusEpoch;
+use crate::state::config::Config;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(epoch: u64, target_epoch: u64)]
+pub struct SweepBonusEpochContext<'info> {
+    #[account(
+        mut,
+        seeds = [BonusEpoch::PREFIX.as_bytes(), epoch.to_le_bytes().as_ref()],
+        bump = bonus_epoch.bump
+    )]
+    pub bonus_epoch: Box<Account<'info, BonusEpoch>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = BonusEpoch::SIZE,
+        seeds = [BonusEpoch::PREFIX.as_bytes(), target_epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub target: Box<Account<'info, BonusEpoch>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: moves what an ended epoch's claims left in its pool (shares nobody claimed within
+/// the claim window, shares of nodes below the reputation threshold and rounding dust) into the pool
+/// of `target_epoch`, which must not have ended. The tokens stay in the vault's bonus pool.
+#[inline(never)]
+pub fn sweep(ctx: Context<SweepBonusEpochContext>, epoch: u64, target_epoch: u64) -> Result<()> {
+    let current = BonusEpoch::current(ctx.accounts.config.params.bonus_epoch_length)?;
+    require!(target_epoch >= current, ErrorCode::InvalidBonusFunding);
+
+    let bonus_epoch = &mut ctx.accounts.bonus_epoch;
+    require!(bonus_epoch.is_sweepable(current), ErrorCode::BonusEpochNotSweepable);
+
+    let remainder = bonus_epoch.unclaimed();
+    bonus_epoch.swept = true;
+
+    let target = &mut ctx.accounts.target;
+    target.bump = ctx.bumps.target;
+    target.epoch = target_epoch;
+    target.funded = target.funded.checked_add(remainder).ok_or(ErrorCode::MathOverflow)?;
+
+    msg!(
+        "Bonus epoch {} swept: {} $SCRAPE moved to epoch {}. Pool: {}",
+        epoch,
+        remainder,
+        target_epoch,
+        target.funded
+    );
+    Ok(())
+}
@@ -27,6 +27,8 @@ pub fn create(ctx: Context<CreateClientContext>) -> Result<()> {
         client.bump = ctx.bumps.client;
         client.owner = signer.key();
         client.task_counter = 0;
+        client.free_allowance_used = 0;
+        client.allowance_period_start = Clock::get()?.unix_timestamp;
     }
 
     msg!("Client created for user: {}", client.owner);
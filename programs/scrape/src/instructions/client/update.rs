@@ -1,19 +1,53 @@
+use crate::events::ClientPeriodRolled;
 use crate::state::client::Client;
+use crate::state::config::Config;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct UpdateClientReportContext<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [Client::PREFIX.as_bytes(), client.owner.as_ref()],
+        bump = client.bump
+    )]
     pub client: Box<Account<'info, Client>>,
     pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
 }
 
+/// Permissionless: closes the client's allowance period once `allowance_period` has elapsed, so the
+/// on-chain ledger reflects the current billing period even without a download.
 #[inline(never)]
 pub fn update_report(ctx: Context<UpdateClientReportContext>) -> Result<()> {
     let client = &mut ctx.accounts.client;
+    let now = Clock::get()?.unix_timestamp;
 
-    // For now, this is a placeholder since Client only has task_counter
-    // In the future, you could add fields like bandwidth_used to Client and update them here
-    msg!("Client report updated for user: {}. Task count: {}", client.owner, client.task_counter);
+    let period_start = client.allowance_period_start;
+    let allowance_used = client.free_allowance_used;
+    if client.roll_period(ctx.accounts.config.params.allowance_period, now) {
+        emit!(ClientPeriodRolled {
+            client: client.owner,
+            period_start,
+            allowance_used,
+            next_period_start: client.allowance_period_start,
+        });
+    }
+
+    msg!(
+        "Client report for user: {}. Tasks: {} ({} open, {} completed). Spent: {} $SCRAPE, {} lamports. Downloaded: {} bytes. Free allowance used: {} MB since {}",
+        client.owner,
+        client.task_counter,
+        client.open_tasks,
+        client.completed_tasks,
+        client.total_spent,
+        client.total_spent_lamports,
+        client.bytes_downloaded,
+        client.free_allowance_used,
+        client.allowance_period_start
+    );
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,38 @@
+use crate::error::ErrorCode;
+use crate::state::config::{Config, ConfigParams};
+use crate::program::Scrape;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeConfigContext<'info> {
+    #[account(
+        init,
+        payer = signer,
+        space = Config::SIZE,
+        seeds = [Config::PREFIX.as_bytes()],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Scrape>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(signer.key()) @ ErrorCode::UnauthorizedDeployer)]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the singleton config. Only the program upgrade authority may call it, so that the first
+/// signer after deployment cannot make itself admin.
+pub fn init(ctx: Context<InitializeConfigContext>, params: ConfigParams) -> Result<()> {
+    require!(params.is_valid(), ErrorCode::InvalidConfig);
+
+    let config = &mut ctx.accounts.config;
+    config.bump = ctx.bumps.config;
+    config.admin = ctx.accounts.signer.key();
+    config.pending_admin = None;
+    config.params = params;
+
+    msg!("Config initialized. Admin: {}, Mint: {}", config.admin, config.params.scrape_mint);
+    Ok(())
+}
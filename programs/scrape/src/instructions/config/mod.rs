@@ -0,0 +1,5 @@
+pub mod init;
+pub mod update;
+
+pub use init::*;
+pub use update::*;
@@ -0,0 +1,58 @@
+use crate::error::ErrorCode;
+use crate::state::config::{Config, ConfigParams};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateConfigContext<'info> {
+    #[account(
+        mut,
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub admin: Signer<'info>,
+}
+
+#[inline(never)]
+pub fn update(ctx: Context<UpdateConfigContext>, params: ConfigParams) -> Result<()> {
+    require!(params.is_valid(), ErrorCode::InvalidConfig);
+
+    let config = &mut ctx.accounts.config;
+    config.params = params;
+
+    msg!("Config updated: {:?}", config.params);
+    Ok(())
+}
+
+/// First step of the admin handover: records `new_admin`, who must then call `accept_admin`.
+#[inline(never)]
+pub fn transfer_admin(ctx: Context<UpdateConfigContext>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pending_admin = Some(new_admin);
+
+    msg!("Config admin transfer proposed: {} -> {}", config.admin, new_admin);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdminContext<'info> {
+    #[account(
+        mut,
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(signer.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+}
+
+#[inline(never)]
+pub fn accept_admin(ctx: Context<AcceptAdminContext>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.signer.key();
+    config.pending_admin = None;
+
+    msg!("Config admin transferred to: {}", config.admin);
+    Ok(())
+}
@@ -1,7 +1,14 @@
 use crate::state::client::Client;
+use crate::state::dataset_listing::DatasetAccess;
+use crate::state::download_receipt::{DownloadReceipt, PaymentMethod};
 use crate::state::task::{Task, TaskStatus};
+use crate::state::token::TokenVault;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
 use crate::error::ErrorCode;
+use crate::state::config::Config;
 
 #[derive(Accounts)]
 pub struct DownloadDatasetContext<'info> {
@@ -11,49 +18,159 @@ pub struct DownloadDatasetContext<'info> {
     )]
     pub task: Box<Account<'info, Task>>,
     #[account(
+        mut,
         seeds = [Client::PREFIX.as_bytes(), signer.key().as_ref()],
         bump
     )]
     pub client: Box<Account<'info, Client>>,
+    #[account(
+        seeds = [DatasetAccess::PREFIX.as_bytes(), task.key().as_ref(), signer.key().as_ref()],
+        bump = dataset_access.bump
+    )]
+    pub dataset_access: Option<Box<Account<'info, DatasetAccess>>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DownloadReceipt::SIZE,
+        seeds = [DownloadReceipt::PREFIX.as_bytes(), task.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub download_receipt: Box<Account<'info, DownloadReceipt>>,
+    #[account(
+        mut,
+        address = config.params.fee_receiver @ ErrorCode::InvalidFeeReceiver
+    )]
+    pub fee_receiver: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), config.params.scrape_mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = config.params.scrape_mint)]
+    pub scrape_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        constraint = user_token_account.mint == config.params.scrape_mint,
+        constraint = user_token_account.owner == signer.key(),
+    )]
+    pub user_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[inline(never)]
-pub fn download(ctx: Context<DownloadDatasetContext>) -> Result<()> {
+pub fn download(ctx: Context<DownloadDatasetContext>, payment: PaymentMethod) -> Result<()> {
     let task = &ctx.accounts.task;
-    let client = &ctx.accounts.client;
+    let client = &mut ctx.accounts.client;
     let signer = &ctx.accounts.signer;
+    let params = &ctx.accounts.config.params;
 
     // Verify the task is completed
     require!(task.status == TaskStatus::Completed, ErrorCode::TaskNotAssigned);
 
-    // Verify the signer is the client who owns the task
-    require!(task.owner == signer.key(), ErrorCode::UnauthorizedNode);
-    require!(task.owner == client.owner, ErrorCode::UnauthorizedNode);
+    // Verify the signer owns the task or has purchased access to its dataset
+    require!(
+        task.owner == signer.key() || ctx.accounts.dataset_access.is_some(),
+        ErrorCode::UnauthorizedNode
+    );
+    require!(client.owner == signer.key(), ErrorCode::UnauthorizedNode);
+
+    // Verify the dataset is available (storage URI exists)
+    require!(task.dataset_uri.is_some(), ErrorCode::TaskNotAssigned);
+
+    // Pricing logic: the free allowance is shared by all of the client's downloads in a period
+    let clock = Clock::get()?;
+    let dataset_size = task.dataset_size_mb();
+    let billable_size = client.consume_free_allowance(
+        dataset_size,
+        params.free_threshold,
+        params.allowance_period,
+        clock.unix_timestamp,
+    );
+
+    let amount = match payment {
+        PaymentMethod::Sol => billable_size.checked_mul(params.rate_per_mb_lamports),
+        PaymentMethod::Scrape => billable_size.checked_mul(params.rate_per_mb_tokens),
+    }
+    .ok_or(ErrorCode::MathOverflow)?;
 
-    // Verify the dataset is available (IPFS hash exists)
-    require!(task.ipfs_hash.is_some(), ErrorCode::TaskNotAssigned);
+    if amount > 0 {
+        match payment {
+            PaymentMethod::Sol => {
+                let cpi_accounts = system_program::Transfer {
+                    from: signer.to_account_info(),
+                    to: ctx.accounts.fee_receiver.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.system_program.to_account_info();
+                let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+                system_program::transfer(cpi_ctx, amount)?;
+            }
+            PaymentMethod::Scrape => {
+                let scrape_mint = ctx
+                    .accounts
+                    .scrape_mint
+                    .as_ref()
+                    .ok_or(ErrorCode::MissingPaymentAccount)?;
+                let vault_token_account = ctx
+                    .accounts
+                    .vault_token_account
+                    .as_mut()
+                    .ok_or(ErrorCode::MissingPaymentAccount)?;
+                let user_token_account = ctx
+                    .accounts
+                    .user_token_account
+                    .as_ref()
+                    .ok_or(ErrorCode::MissingPaymentAccount)?;
+                let received = transfer_tokens(
+                    &ctx.accounts.token_program,
+                    user_token_account,
+                    vault_token_account,
+                    scrape_mint,
+                    signer.to_account_info(),
+                    &[],
+                    amount,
+                )?;
 
-    // Pricing logic (same as your previous implementation)
-    const FREE_THRESHOLD: u64 = 500; // 500 MB
-    const RATE_PER_MB_LAMPORTS: u64 = 5_000_000; // 0.005 SOL per MB = 5,000,000 lamports
+                let token_vault = &mut ctx.accounts.token_vault;
+                token_vault.total_fees_collected = token_vault
+                    .total_fees_collected
+                    .checked_add(received)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                token_vault.credit_treasury(received)?;
+            }
+        }
+    }
 
-    let dataset_size = task.dataset_size;
-    let cost_lamports = if dataset_size <= FREE_THRESHOLD {
-        0
-    } else {
-        (dataset_size - FREE_THRESHOLD) * RATE_PER_MB_LAMPORTS
-    };
+    client.record_download(task.dataset_size, amount, payment)?;
+
+    // Record the payment for off-chain gateways
+    let download_receipt = &mut ctx.accounts.download_receipt;
+    download_receipt.bump = ctx.bumps.download_receipt;
+    download_receipt.task = task.key();
+    download_receipt.buyer = signer.key();
+    download_receipt.amount = amount;
+    download_receipt.payment = payment;
+    download_receipt.slot = clock.slot;
 
-    // In a real implementation, you might transfer lamports to a FeeReceiver here
-    // For now, we log the cost and assume the client handles the actual download off-chain
     msg!(
-        "Dataset download initiated for task {}. IPFS Hash: {}. Size: {} MB. Cost: {} lamports.",
+        "Dataset download paid for task {}. URI: {}. Size: {} MB ({} MB billable). Paid: {} ({:?}).",
         task.id,
-        task.ipfs_hash.as_ref().unwrap(),
+        task.dataset_uri.as_ref().unwrap(),
         dataset_size,
-        cost_lamports
+        billable_size,
+        amount,
+        payment
     );
 
     Ok(())
-}
\ No newline at end of file
+}
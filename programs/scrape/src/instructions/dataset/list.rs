@@ -0,0 +1,85 @@
+use crate::state::dataset_listing::{DatasetLicense, DatasetListing};
+use crate::state::task::{Task, TaskStatus};
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ListDatasetContext<'info> {
+    #[account(
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = task.owner == signer.key() @ ErrorCode::UnauthorizedNode,
+        constraint = task.status == TaskStatus::Completed @ ErrorCode::TaskNotAssigned
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DatasetListing::SIZE,
+        seeds = [DatasetListing::PREFIX.as_bytes(), task.key().as_ref()],
+        bump
+    )]
+    pub dataset_listing: Box<Account<'info, DatasetListing>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(never)]
+pub fn list(ctx: Context<ListDatasetContext>, price: u64, license: DatasetLicense) -> Result<()> {
+    let task = &ctx.accounts.task;
+    let dataset_listing = &mut ctx.accounts.dataset_listing;
+
+    require!(task.dataset_uri.is_some(), ErrorCode::TaskNotAssigned);
+    require!(price > 0, ErrorCode::InvalidPrice);
+
+    dataset_listing.bump = ctx.bumps.dataset_listing;
+    dataset_listing.task = task.key();
+    dataset_listing.seller = task.owner;
+    dataset_listing.provider = task.node_assigned.ok_or(ErrorCode::TaskNotAssigned)?;
+    dataset_listing.price = price;
+    dataset_listing.license = license;
+    dataset_listing.active = true;
+    dataset_listing.sales = 0;
+
+    msg!("Dataset for task {} listed at {} $SCRAPE ({:?})", task.id, price, license);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateListingContext<'info> {
+    #[account(
+        mut,
+        seeds = [DatasetListing::PREFIX.as_bytes(), dataset_listing.task.as_ref()],
+        bump = dataset_listing.bump,
+        constraint = dataset_listing.seller == signer.key() @ ErrorCode::UnauthorizedNode
+    )]
+    pub dataset_listing: Box<Account<'info, DatasetListing>>,
+    pub signer: Signer<'info>,
+}
+
+/// Changes price or license, or pauses sales with `active = false`. Existing access receipts are unaffected.
+#[inline(never)]
+pub fn update_listing(
+    ctx: Context<UpdateListingContext>,
+    price: u64,
+    license: DatasetLicense,
+    active: bool,
+) -> Result<()> {
+    let dataset_listing = &mut ctx.accounts.dataset_listing;
+
+    require!(price > 0, ErrorCode::InvalidPrice);
+
+    dataset_listing.price = price;
+    dataset_listing.license = license;
+    dataset_listing.active = active;
+
+    msg!(
+        "Dataset listing {} updated. Price: {} $SCRAPE, License: {:?}, Active: {}",
+        dataset_listing.key(),
+        price,
+        license,
+        active
+    );
+    Ok(())
+}
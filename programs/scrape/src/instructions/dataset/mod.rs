@@ -1,5 +1,9 @@
 pub mod download;
 pub mod preview;
+pub mod list;
+pub mod purchase;
 
 pub use download::*;
-pub use preview::*;
\ No newline at end of file
+pub use preview::*;
+pub use list::*;
+pub use purchase::*;
@@ -1,4 +1,5 @@
 use crate::state::client::Client;
+use crate::state::dataset_listing::DatasetAccess;
 use crate::state::task::{Task, TaskStatus};
 use anchor_lang::prelude::*;
 use crate::error::ErrorCode;
@@ -15,6 +16,11 @@ pub struct PreviewDatasetContext<'info> {
         bump
     )]
     pub client: Box<Account<'info, Client>>,
+    #[account(
+        seeds = [DatasetAccess::PREFIX.as_bytes(), task.key().as_ref(), signer.key().as_ref()],
+        bump = dataset_access.bump
+    )]
+    pub dataset_access: Option<Box<Account<'info, DatasetAccess>>>,
     pub signer: Signer<'info>,
 }
 
@@ -27,19 +33,23 @@ pub fn preview(ctx: Context<PreviewDatasetContext>) -> Result<()> {
     // Verify the task is completed
     require!(task.status == TaskStatus::Completed, ErrorCode::TaskNotAssigned);
 
-    // Verify the signer is the client who owns the task
-    require!(task.owner == signer.key(), ErrorCode::UnauthorizedNode);
-    require!(task.owner == client.owner, ErrorCode::UnauthorizedNode);
+    // Verify the signer owns the task or has purchased access to its dataset
+    require!(
+        task.owner == signer.key() || ctx.accounts.dataset_access.is_some(),
+        ErrorCode::UnauthorizedNode
+    );
+    require!(client.owner == signer.key(), ErrorCode::UnauthorizedNode);
 
-    // Verify the dataset is available (IPFS hash exists)
-    require!(task.ipfs_hash.is_some(), ErrorCode::TaskNotAssigned);
+    // Verify the dataset is available (storage URI exists)
+    require!(task.dataset_uri.is_some(), ErrorCode::TaskNotAssigned);
 
     // Log the preview action (actual preview would happen off-chain)
     msg!(
-        "Dataset preview initiated for task {}. IPFS Hash: {}. Size: {} MB.",
+        "Dataset preview initiated for task {}. URI: {}. Size: {} bytes, {} records.",
         task.id,
-        task.ipfs_hash.as_ref().unwrap(),
-        task.dataset_size
+        task.dataset_uri.as_ref().unwrap(),
+        task.dataset_size,
+        task.record_count
     );
 
     Ok(())
@@ -0,0 +1,145 @@
+use crate::state::config::Config;
+use crate::state::dataset_listing::{DatasetAccess, DatasetListing};
+use crate::state::task::{Task, TaskStatus};
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct PurchaseDatasetContext<'info> {
+    #[account(
+        mut,
+        seeds = [DatasetListing::PREFIX.as_bytes(), dataset_listing.task.as_ref()],
+        bump = dataset_listing.bump,
+        constraint = dataset_listing.active @ ErrorCode::ListingInactive,
+        constraint = dataset_listing.seller != signer.key() @ ErrorCode::UnauthorizedNode
+    )]
+    pub dataset_listing: Box<Account<'info, DatasetListing>>,
+    // The listed task must still hold its dataset; a closed task leaves nothing to download
+    #[account(
+        address = dataset_listing.task,
+        constraint = task.status == TaskStatus::Completed @ ErrorCode::TaskNotAssigned
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DatasetAccess::SIZE,
+        seeds = [DatasetAccess::PREFIX.as_bytes(), dataset_listing.task.as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub dataset_access: Box<Account<'info, DatasetAccess>>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), config.params.scrape_mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = config.params.scrape_mint)]
+    pub scrape_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = buyer_token_account.mint == config.params.scrape_mint,
+        constraint = buyer_token_account.owner == signer.key(),
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == config.params.scrape_mint,
+        constraint = seller_token_account.owner == dataset_listing.seller,
+    )]
+    pub seller_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = provider_token_account.mint == config.params.scrape_mint,
+        constraint = provider_token_account.owner == dataset_listing.provider,
+    )]
+    pub provider_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Buys access to a listed dataset; proceeds are split between protocol, provider node and task owner.
+/// Fails if the price was raised above `max_price` after the buyer saw the listing.
+#[inline(never)]
+pub fn purchase(ctx: Context<PurchaseDatasetContext>, max_price: u64) -> Result<()> {
+    let dataset_listing = &mut ctx.accounts.dataset_listing;
+    let params = &ctx.accounts.config.params;
+    let price = dataset_listing.price;
+    require!(price <= max_price, ErrorCode::PriceAboveMax);
+
+    let share_of = |bps: u16| -> Result<u64> {
+        Ok((price as u128)
+            .checked_mul(bps as u128)
+            .and_then(|amount| amount.checked_div(crate::BPS_DENOMINATOR as u128))
+            .ok_or(ErrorCode::MathOverflow)? as u64)
+    };
+    let protocol_fee = share_of(params.protocol_fee_bps)?;
+    let provider_share = share_of(params.market_provider_bps)?;
+    let seller_share = price
+        .checked_sub(protocol_fee)
+        .and_then(|amount| amount.checked_sub(provider_share))
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Only what reaches the vault after any transfer fee is credited to the treasury
+    let mut fee_received = 0;
+    let payouts = [
+        (&mut ctx.accounts.vault_token_account, protocol_fee),
+        (&mut ctx.accounts.provider_token_account, provider_share),
+        (&mut ctx.accounts.seller_token_account, seller_share),
+    ];
+    for (index, (destination, amount)) in payouts.into_iter().enumerate() {
+        if amount == 0 {
+            continue;
+        }
+        let received = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.buyer_token_account,
+            destination,
+            &ctx.accounts.scrape_mint,
+            ctx.accounts.signer.to_account_info(),
+            &[],
+            amount,
+        )?;
+        if index == 0 {
+            fee_received = received;
+        }
+    }
+
+    let token_vault = &mut ctx.accounts.token_vault;
+    token_vault.total_fees_collected = token_vault
+        .total_fees_collected
+        .checked_add(fee_received)
+        .ok_or(ErrorCode::MathOverflow)?;
+    token_vault.credit_treasury(fee_received)?;
+    dataset_listing.sales = dataset_listing.sales.saturating_add(1);
+
+    let dataset_access = &mut ctx.accounts.dataset_access;
+    dataset_access.bump = ctx.bumps.dataset_access;
+    dataset_access.task = dataset_listing.task;
+    dataset_access.buyer = ctx.accounts.signer.key();
+    dataset_access.price_paid = price;
+    dataset_access.license = dataset_listing.license;
+    dataset_access.purchased_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Dataset access purchased by {} for task {}. Seller share: {}, Provider share: {}, Protocol fee: {} $SCRAPE",
+        dataset_access.buyer,
+        dataset_access.task,
+        seller_share,
+        provider_share,
+        protocol_fee
+    );
+    Ok(())
+}
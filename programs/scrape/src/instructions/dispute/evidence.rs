@@ -0,0 +1,33 @@
+use crate::error::ErrorCode;
+use crate::state::dispute::{Dispute, DisputeStatus};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SubmitDisputeEvidenceContext<'info> {
+    #[account(
+        mut,
+        seeds = [Dispute::PREFIX.as_bytes(), dispute.task.as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.status == DisputeStatus::Open @ ErrorCode::DisputeNotOpen
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+    pub signer: Signer<'info>,
+}
+
+/// Lets either party replace its evidence hash while the dispute is open.
+#[inline(never)]
+pub fn submit_evidence(ctx: Context<SubmitDisputeEvidenceContext>, evidence_hash: [u8; 32]) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute;
+    let signer = ctx.accounts.signer.key();
+
+    if signer == dispute.client {
+        dispute.client_evidence = evidence_hash;
+    } else if signer == dispute.provider {
+        dispute.provider_evidence = evidence_hash;
+    } else {
+        return err!(ErrorCode::UnauthorizedNode);
+    }
+
+    msg!("Evidence submitted for dispute {} by: {}", dispute.key(), signer);
+    Ok(())
+}
@@ -0,0 +1,9 @@
+pub mod open;
+pub mod evidence;
+pub mod vote;
+pub mod settle;
+
+pub use open::*;
+pub use evidence::*;
+pub use vote::*;
+pub use settle::*;
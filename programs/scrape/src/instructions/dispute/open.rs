@@ -0,0 +1,84 @@
+use crate::error::ErrorCode;
+use crate::state::dispute::{Dispute, DisputeStatus};
+use crate::state::task::{Task, TaskStatus};
+use anchor_lang::prelude::*;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct OpenDisputeContext<'info> {
+    #[account(
+        mut,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        init,
+        payer = signer,
+        space = Dispute::SIZE,
+        seeds = [Dispute::PREFIX.as_bytes(), task.key().as_ref()],
+        bump
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Sends a result to arbitration; the escrow stays locked until settlement. The owner may contest a
+/// submitted result instead of accepting or rejecting it, and the provider may contest a rejection.
+/// Either party has `review_window` from the submission or rejection respectively.
+#[inline(never)]
+pub fn open(ctx: Context<OpenDisputeContext>, evidence_hash: [u8; 32]) -> Result<()> {
+    let task = &mut ctx.accounts.task;
+    let dispute = &mut ctx.accounts.dispute;
+    let signer = ctx.accounts.signer.key();
+
+    let provider = task.node_assigned.ok_or(ErrorCode::TaskNotAssigned)?;
+    let opened_at = match task.status {
+        TaskStatus::Submitted => {
+            require_keys_eq!(signer, task.owner, ErrorCode::UnauthorizedNode);
+            task.submitted_at
+        }
+        TaskStatus::Rejected => {
+            require_keys_eq!(signer, provider, ErrorCode::UnauthorizedNode);
+            task.rejected_at
+        }
+        _ => return err!(ErrorCode::ResultNotSubmitted),
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let window_closes_at = opened_at
+        .checked_add(ctx.accounts.config.params.review_window)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(now <= window_closes_at, ErrorCode::ReviewWindowClosed);
+
+    let (client_evidence, provider_evidence) = if signer == task.owner {
+        (evidence_hash, [0; 32])
+    } else {
+        ([0; 32], evidence_hash)
+    };
+
+    dispute.bump = ctx.bumps.dispute;
+    dispute.task = task.key();
+    dispute.client = task.owner;
+    dispute.provider = provider;
+    dispute.client_evidence = client_evidence;
+    dispute.provider_evidence = provider_evidence;
+    dispute.status = DisputeStatus::Open;
+    dispute.votes = Vec::new();
+    dispute.provider_share_bps = 0;
+    dispute.opened_at = now;
+    dispute.settled_at = 0;
+    dispute.opened_by = signer;
+
+    task.status = TaskStatus::Disputed;
+
+    msg!("Dispute opened for task {} by: {}. Owner: {}, node: {}", task.id, signer, task.owner, provider);
+    Ok(())
+}
@@ -0,0 +1,292 @@
+use crate::error::ErrorCode;
+use crate::instructions::stake::slash_stake;
+use crate::state::arbiter_registry::ArbiterRegistry;
+use crate::state::dispute::{Dispute, DisputeStatus};
+use crate::state::provider_node::ProviderNode;
+use crate::state::reputation::{ReputationConfig, ReputationEvent};
+use crate::state::task::{Task, TaskStatus};
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
+use crate::state::bonus::{record_contribution, BonusClaim, BonusEpoch};
+use crate::state::client::Client;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct SettleDisputeContext<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [Dispute::PREFIX.as_bytes(), task.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.status == DisputeStatus::Open @ ErrorCode::DisputeNotOpen
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+    /// CHECK: Party that opened the dispute and receives its rent back, validated against `dispute.rent_payer()`
+    #[account(mut, address = dispute.rent_payer())]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = task.status == TaskStatus::Disputed @ ErrorCode::DisputeNotOpen
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        mut,
+        seeds = [Client::PREFIX.as_bytes(), task.owner.as_ref()],
+        bump = client.bump
+    )]
+    pub client: Box<Account<'info, Client>>,
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), dispute.provider.as_ref()],
+        bump
+    )]
+    pub node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        mut,
+        seeds = [ProviderNode::STAKE_PREFIX.as_bytes(), node.key().as_ref()],
+        bump
+    )]
+    pub stake_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [ArbiterRegistry::PREFIX.as_bytes()],
+        bump = arbiter_registry.bump
+    )]
+    pub arbiter_registry: Box<Account<'info, ArbiterRegistry>>,
+    #[account(
+        seeds = [ReputationConfig::PREFIX.as_bytes()],
+        bump = reputation_config.bump
+    )]
+    pub reputation_config: Box<Account<'info, ReputationConfig>>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), task.payment_mint.as_ref()],
+        bump = token_vault.bump,
+        constraint = !token_vault.paused @ ErrorCode::VaultPaused
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = task.payment_mint)]
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = node_token_account.mint == task.payment_mint,
+        constraint = node_token_account.owner == dispute.provider,
+    )]
+    pub node_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = user_token_account.mint == task.payment_mint,
+        constraint = user_token_account.owner == dispute.client,
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    // The $SCRAPE vault that receives slashed stake; only needed when the task pays in another mint
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), config.params.scrape_mint.as_ref()],
+        bump = stake_vault.bump
+    )]
+    pub stake_vault: Option<Box<Account<'info, TokenVault>>>,
+    #[account(address = config.params.scrape_mint)]
+    pub stake_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub stake_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub stake_token_program: Option<Interface<'info, TokenInterface>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = BonusEpoch::SIZE,
+        seeds = [
+            BonusEpoch::PREFIX.as_bytes(),
+            BonusEpoch::current(config.params.bonus_epoch_length)?.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bonus_epoch: Box<Account<'info, BonusEpoch>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = BonusClaim::SIZE,
+        seeds = [
+            BonusClaim::PREFIX.as_bytes(),
+            node.owner.as_ref(),
+            BonusEpoch::current(config.params.bonus_epoch_length)?.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bonus_claim: Box<Account<'info, BonusClaim>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless once quorum is reached: splits the escrow by the averaged arbiter vote and closes
+/// the dispute, so that a later result for the same task can be disputed again. A dispute still
+/// short of quorum after `dispute_timeout` is split evenly, without slashing or reputation changes.
+#[inline(never)]
+pub fn settle(ctx: Context<SettleDisputeContext>) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute;
+    let task = &mut ctx.accounts.task;
+    let node = &mut ctx.accounts.node;
+    let token_vault = &mut ctx.accounts.token_vault;
+
+    let now = Clock::get()?.unix_timestamp;
+    let (votes, tallied_share_bps) = dispute.tally(&ctx.accounts.arbiter_registry);
+    let has_quorum = votes >= ctx.accounts.arbiter_registry.quorum as usize;
+    let provider_share_bps = if has_quorum {
+        tallied_share_bps
+    } else {
+        require!(
+            dispute.has_timed_out(now, ctx.accounts.config.params.dispute_timeout),
+            ErrorCode::QuorumNotReached
+        );
+        Dispute::TIMEOUT_PROVIDER_SHARE_BPS
+    };
+
+    let escrow = task.escrow;
+    let provider_amount = (escrow as u128)
+        .checked_mul(provider_share_bps as u128)
+        .and_then(|amount| amount.checked_div(crate::BPS_DENOMINATOR as u128))
+        .ok_or(ErrorCode::MathOverflow)? as u64;
+    let client_amount = escrow - provider_amount;
+    let fee = ctx.accounts.config.params.task_fee(provider_amount)?;
+    let provider_payout = provider_amount - fee;
+
+    token_vault.release_escrow(escrow)?;
+    token_vault.credit_treasury(fee)?;
+
+    let mint = token_vault.mint;
+    let bump = [token_vault.bump];
+    let seeds: &[&[u8]] = &[TokenVault::PREFIX.as_bytes(), mint.as_ref(), &bump];
+
+    if provider_payout > 0 {
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault_token_account,
+            &mut ctx.accounts.node_token_account,
+            &ctx.accounts.payment_mint,
+            token_vault.to_account_info(),
+            &[seeds],
+            provider_payout,
+        )?;
+    }
+
+    if client_amount > 0 {
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault_token_account,
+            &mut ctx.accounts.user_token_account,
+            &ctx.accounts.payment_mint,
+            token_vault.to_account_info(),
+            &[seeds],
+            client_amount,
+        )?;
+    }
+
+    token_vault.total_rewards_distributed = token_vault
+        .total_rewards_distributed
+        .checked_add(provider_payout)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let scrape_mint = ctx.accounts.config.params.scrape_mint;
+    node.rewards = node
+        .rewards
+        .checked_add(task.in_scrape(&scrape_mint, provider_payout))
+        .ok_or(ErrorCode::MathOverflow)?;
+    task.escrow = 0;
+    task.status = TaskStatus::Completed;
+    ctx.accounts.client.close_task(task.in_scrape(&scrape_mint, provider_amount), true)?;
+
+    // The node's contribution is the share of the dataset the arbiters paid it for
+    let contribution = (task.bonus_contribution() as u128)
+        .checked_mul(provider_share_bps as u128)
+        .and_then(|amount| amount.checked_div(crate::BPS_DENOMINATOR as u128))
+        .ok_or(ErrorCode::MathOverflow)? as u64;
+    record_contribution(
+        &mut ctx.accounts.bonus_epoch,
+        ctx.bumps.bonus_epoch,
+        &mut ctx.accounts.bonus_claim,
+        ctx.bumps.bonus_claim,
+        node.owner,
+        BonusEpoch::current(ctx.accounts.config.params.bonus_epoch_length)?,
+        contribution,
+    )?;
+    node.active_tasks = node.active_tasks.saturating_sub(1);
+
+    // A provider awarded less than half of the escrow lost the dispute
+    if !has_quorum {
+        msg!("Dispute for task {} timed out without quorum", task.id);
+    } else if (provider_share_bps as u64) * 2 < crate::BPS_DENOMINATOR as u64 {
+        node.record_reputation_event(
+            &ctx.accounts.reputation_config.weights,
+            ReputationEvent::DisputeLost,
+            now,
+        );
+        if task.payment_mint == scrape_mint {
+            slash_stake(
+                node,
+                &ctx.accounts.stake_token_account,
+                token_vault,
+                &ctx.accounts.payment_mint,
+                &mut ctx.accounts.vault_token_account,
+                &ctx.accounts.token_program,
+                &ctx.accounts.config.params,
+                ctx.accounts.config.params.dispute_slash_bps,
+            )?;
+        } else {
+            let (Some(stake_vault), Some(stake_mint), Some(stake_vault_token_account), Some(stake_token_program)) = (
+                ctx.accounts.stake_vault.as_mut(),
+                ctx.accounts.stake_mint.as_ref(),
+                ctx.accounts.stake_vault_token_account.as_mut(),
+                ctx.accounts.stake_token_program.as_ref(),
+            ) else {
+                return err!(ErrorCode::MissingStakeVault);
+            };
+            require_keys_eq!(
+                stake_vault_token_account.key(),
+                stake_vault.token_account,
+                ErrorCode::MissingStakeVault
+            );
+            slash_stake(
+                node,
+                &ctx.accounts.stake_token_account,
+                stake_vault,
+                stake_mint,
+                stake_vault_token_account,
+                stake_token_program,
+                &ctx.accounts.config.params,
+                ctx.accounts.config.params.dispute_slash_bps,
+            )?;
+        }
+    } else {
+        node.record_reputation_event(
+            &ctx.accounts.reputation_config.weights,
+            ReputationEvent::TaskAccepted,
+            now,
+        );
+    }
+
+    dispute.provider_share_bps = provider_share_bps;
+    dispute.status = DisputeStatus::Settled;
+    dispute.settled_at = now;
+
+    msg!(
+        "Dispute for task {} settled: {} to node {}, {} refunded to owner {}. Reputation: {}",
+        task.id,
+        provider_amount,
+        dispute.provider,
+        client_amount,
+        dispute.client,
+        node.reputation
+    );
+    Ok(())
+}
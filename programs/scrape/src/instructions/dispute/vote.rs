@@ -0,0 +1,49 @@
+use crate::error::ErrorCode;
+use crate::state::arbiter_registry::ArbiterRegistry;
+use crate::state::dispute::{ArbiterVote, Dispute, DisputeStatus};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct VoteDisputeContext<'info> {
+    #[account(
+        mut,
+        seeds = [Dispute::PREFIX.as_bytes(), dispute.task.as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.status == DisputeStatus::Open @ ErrorCode::DisputeNotOpen
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+    #[account(
+        seeds = [ArbiterRegistry::PREFIX.as_bytes()],
+        bump = arbiter_registry.bump,
+        constraint = arbiter_registry.is_arbiter(&signer.key()) @ ErrorCode::NotAnArbiter
+    )]
+    pub arbiter_registry: Box<Account<'info, ArbiterRegistry>>,
+    pub signer: Signer<'info>,
+}
+
+#[inline(never)]
+pub fn vote(ctx: Context<VoteDisputeContext>, provider_share_bps: u16) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute;
+    let arbiter = ctx.accounts.signer.key();
+
+    require!(provider_share_bps <= crate::BPS_DENOMINATOR, ErrorCode::InvalidShare);
+    require!(!dispute.has_voted(&arbiter), ErrorCode::AlreadyVoted);
+    require!(
+        dispute.votes.len() < ArbiterRegistry::MAX_ARBITERS,
+        ErrorCode::ArbiterRegistryFull
+    );
+
+    dispute.votes.push(ArbiterVote {
+        arbiter,
+        provider_share_bps,
+    });
+
+    msg!(
+        "Arbiter {} voted {} bps to provider on dispute {}. Votes: {}",
+        arbiter,
+        provider_share_bps,
+        dispute.key(),
+        dispute.votes.len()
+    );
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::state::endpoint_node::EndpointNode;
 use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
 
 #[derive(Accounts)]
 pub struct CreateEndpointNodeContext<'info> {
@@ -18,12 +19,15 @@ pub struct CreateEndpointNodeContext<'info> {
 }
 
 #[inline(never)]
-pub fn create(ctx: Context<CreateEndpointNodeContext>) -> Result<()> {
+pub fn create(ctx: Context<CreateEndpointNodeContext>, encryption_key: [u8; 32]) -> Result<()> {
+    require!(encryption_key != [0; 32], ErrorCode::MissingEncryptionKey);
+
     let signer = &ctx.accounts.signer;
     let endpoint_node = &mut ctx.accounts.endpoint_node;
 
     endpoint_node.bump = ctx.bumps.endpoint_node;
     endpoint_node.owner = signer.key();
+    endpoint_node.encryption_key = encryption_key;
 
     msg!("EndpointNode created for user: {}", endpoint_node.owner);
     Ok(())
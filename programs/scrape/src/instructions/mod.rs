@@ -18,3 +18,27 @@ pub use dataset::*;
 
 pub mod node_registry;
 pub use node_registry::*;
+
+pub mod arbiter_registry;
+pub use arbiter_registry::*;
+
+pub mod dispute;
+pub use dispute::*;
+
+pub mod stake;
+pub use stake::*;
+
+pub mod reputation_config;
+pub use reputation_config::*;
+
+pub mod config;
+pub use config::*;
+
+pub mod randomness_feed;
+pub use randomness_feed::*;
+
+pub mod bonus;
+pub use bonus::*;
+
+pub mod treasury;
+pub use treasury::*;
@@ -0,0 +1,81 @@
+use crate::error::ErrorCode;
+use crate::state::node_registry::{NodeRegistry, RegistryPage};
+use crate::state::provider_node::ProviderNode;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(target_index: u32)]
+pub struct CompactRegistryContext<'info> {
+    #[account(
+        mut,
+        seeds = [NodeRegistry::PREFIX.as_bytes()],
+        bump = node_registry.bump
+    )]
+    pub node_registry: Box<Account<'info, NodeRegistry>>,
+    #[account(
+        mut,
+        seeds = [
+            RegistryPage::PREFIX.as_bytes(),
+            node_registry.page_count.saturating_sub(1).to_le_bytes().as_ref()
+        ],
+        bump = last_page.bump
+    )]
+    pub last_page: Box<Account<'info, RegistryPage>>,
+    #[account(
+        mut,
+        seeds = [RegistryPage::PREFIX.as_bytes(), target_index.to_le_bytes().as_ref()],
+        bump = target_page.bump
+    )]
+    pub target_page: Option<Box<Account<'info, RegistryPage>>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    // remaining_accounts: ProviderNodes on the last page to move into the target page (writable)
+}
+
+/// Moves nodes off the last page into free slots of an earlier page and closes the last page once
+/// it is empty, refunding its rent to the signer. Anyone may compact. Moving nodes bumps the registry
+/// epoch, which makes draws in progress process the pages again with the seed they already have.
+#[inline(never)]
+pub fn compact<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CompactRegistryContext<'info>>,
+    target_index: u32,
+) -> Result<()> {
+    let node_registry = &mut ctx.accounts.node_registry;
+    let last_page = &mut ctx.accounts.last_page;
+
+    if !ctx.remaining_accounts.is_empty() {
+        let target_page = ctx
+            .accounts
+            .target_page
+            .as_mut()
+            .ok_or(ErrorCode::InvalidRegistryPage)?;
+        require!(target_index < last_page.index, ErrorCode::InvalidRegistryPage);
+
+        for account in ctx.remaining_accounts.iter() {
+            let mut node = Account::<ProviderNode>::try_from(account)?;
+            require!(node.registry_page == Some(last_page.index), ErrorCode::NodeNotRegistered);
+            require!(last_page.remove(&node.owner), ErrorCode::NodeNotRegistered);
+            require!(target_page.insert(node.owner), ErrorCode::RegistryPageFull);
+
+            node.registry_page = Some(target_index);
+            node.exit(&crate::ID)?;
+        }
+
+        // Draws in progress may already have seen the moved nodes
+        node_registry.epoch = node_registry.epoch.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        msg!(
+            "Moved {} nodes from page {} to page {}",
+            ctx.remaining_accounts.len(),
+            last_page.index,
+            target_index
+        );
+    }
+
+    if last_page.count == 0 {
+        let index = last_page.index;
+        node_registry.page_count -= 1;
+        last_page.close(ctx.accounts.signer.to_account_info())?;
+        msg!("Registry page {} closed. Pages: {}", index, node_registry.page_count);
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::error::ErrorCode;
+use crate::state::node_registry::{NodeRegistry, RegistryPage};
+use crate::state::provider_node::ProviderNode;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DeregisterNodeContext<'info> {
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), signer.key().as_ref()],
+        bump,
+        constraint = provider_node.owner == signer.key() @ ErrorCode::UnauthorizedNode
+    )]
+    pub provider_node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        mut,
+        seeds = [NodeRegistry::PREFIX.as_bytes()],
+        bump = node_registry.bump
+    )]
+    pub node_registry: Box<Account<'info, NodeRegistry>>,
+    #[account(
+        mut,
+        seeds = [
+            RegistryPage::PREFIX.as_bytes(),
+            provider_node.registry_page.unwrap_or_default().to_le_bytes().as_ref()
+        ],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Box<Account<'info, RegistryPage>>,
+    pub signer: Signer<'info>,
+}
+
+/// Removes the node from the registry so it is no longer selected for tasks.
+#[inline(never)]
+pub fn deregister(ctx: Context<DeregisterNodeContext>) -> Result<()> {
+    remove_from_registry(
+        &mut ctx.accounts.node_registry,
+        &mut ctx.accounts.registry_page,
+        &mut ctx.accounts.provider_node,
+    )
+}
+
+/// Frees the node's slot. The page itself is reclaimed by `compact_registry`.
+pub fn remove_from_registry(
+    node_registry: &mut NodeRegistry,
+    registry_page: &mut RegistryPage,
+    provider_node: &mut ProviderNode,
+) -> Result<()> {
+    require!(
+        provider_node.registry_page == Some(registry_page.index),
+        ErrorCode::NodeNotRegistered
+    );
+    require!(registry_page.remove(&provider_node.owner), ErrorCode::NodeNotRegistered);
+
+    node_registry.node_count -= 1;
+    provider_node.registry_page = None;
+
+    msg!(
+        "ProviderNode {} removed from page {}. Total nodes: {}",
+        provider_node.owner,
+        registry_page.index,
+        node_registry.node_count
+    );
+    Ok(())
+}
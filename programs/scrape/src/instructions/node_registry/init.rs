@@ -6,7 +6,7 @@ pub struct NodeRegistryInitializeContext<'info> {
     #[account(
         init,
         payer = signer,
-        space = NodeRegistry::calculate_size(0), // Start with space for 0 nodes
+        space = NodeRegistry::SIZE,
         seeds = [NodeRegistry::PREFIX.as_bytes()],
         bump
     )]
@@ -19,6 +19,7 @@ pub struct NodeRegistryInitializeContext<'info> {
 pub fn noderegistryinitialize(ctx: Context<NodeRegistryInitializeContext>) -> Result<()> {
     let node_registry = &mut ctx.accounts.node_registry;
     node_registry.bump = ctx.bumps.node_registry;
-    node_registry.nodes = Vec::new();
+    node_registry.page_count = 0;
+    node_registry.node_count = 0;
     Ok(())
-}
\ No newline at end of file
+}
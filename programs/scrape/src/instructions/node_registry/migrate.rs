@@ -0,0 +1,74 @@
+use crate::error::ErrorCode;
+use crate::state::config::Config;
+use crate::state::node_registry::NodeRegistry;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+pub struct MigrateNodeRegistryContext<'info> {
+    /// CHECK: the legacy layout (bump, Vec<Pubkey>) cannot be deserialized as the current one; the
+    /// discriminator and size are checked before the account is rewritten
+    #[account(
+        mut,
+        owner = crate::ID @ ErrorCode::InvalidRegistryMigration,
+        seeds = [NodeRegistry::PREFIX.as_bytes()],
+        bump
+    )]
+    pub node_registry: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Rewrites a NodeRegistry created under the legacy single-list layout as an empty paged registry,
+/// resizing it and settling the rent difference with the admin. The legacy list is dropped: its
+/// nodes are not on any page yet and list themselves again with `register_node`.
+#[inline(never)]
+pub fn migrate(ctx: Context<MigrateNodeRegistryContext>) -> Result<()> {
+    let info = ctx.accounts.node_registry.to_account_info();
+    // Legacy sizes are 13 + 32 * n bytes, which never equals the current size
+    require!(
+        info.try_borrow_data()?.starts_with(NodeRegistry::DISCRIMINATOR)
+            && info.data_len() != NodeRegistry::SIZE,
+        ErrorCode::InvalidRegistryMigration
+    );
+    let bump = *info
+        .try_borrow_data()?
+        .get(8)
+        .ok_or(ErrorCode::InvalidRegistryMigration)?;
+    let legacy_nodes = info
+        .try_borrow_data()?
+        .get(9..13)
+        .map_or(0, |len| u32::from_le_bytes(len.try_into().unwrap()));
+
+    let required = Rent::get()?.minimum_balance(NodeRegistry::SIZE);
+    let balance = info.lamports();
+    if balance < required {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.admin.to_account_info(),
+            to: info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, required - balance)?;
+    } else {
+        **info.try_borrow_mut_lamports()? = required;
+        **ctx.accounts.admin.to_account_info().try_borrow_mut_lamports()? += balance - required;
+    }
+    info.realloc(NodeRegistry::SIZE, true)?;
+
+    let node_registry = NodeRegistry {
+        bump,
+        ..Default::default()
+    };
+    node_registry.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    msg!("NodeRegistry migrated, {} legacy nodes must register again", legacy_nodes);
+    Ok(())
+}
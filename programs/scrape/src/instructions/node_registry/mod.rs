@@ -1,3 +1,11 @@
 pub mod init;
+pub mod register;
+pub mod deregister;
+pub mod compact;
+pub mod migrate;
 
-pub use init::*;
\ No newline at end of file
+pub use init::*;
+pub use register::*;
+pub use deregister::*;
+pub use compact::*;
+pub use migrate::*;
@@ -0,0 +1,78 @@
+use crate::error::ErrorCode;
+use crate::state::node_registry::{NodeRegistry, RegistryPage};
+use crate::state::provider_node::ProviderNode;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(page_index: u32)]
+pub struct RegisterNodeContext<'info> {
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), signer.key().as_ref()],
+        bump,
+        constraint = provider_node.owner == signer.key() @ ErrorCode::UnauthorizedNode
+    )]
+    pub provider_node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        mut,
+        seeds = [NodeRegistry::PREFIX.as_bytes()],
+        bump = node_registry.bump
+    )]
+    pub node_registry: Box<Account<'info, NodeRegistry>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = RegistryPage::SIZE,
+        seeds = [RegistryPage::PREFIX.as_bytes(), page_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub registry_page: Box<Account<'info, RegistryPage>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Lists a previously deregistered node again so it can be selected for tasks.
+#[inline(never)]
+pub fn register(ctx: Context<RegisterNodeContext>, page_index: u32) -> Result<()> {
+    let page_bump = ctx.bumps.registry_page;
+    add_to_registry(
+        &mut ctx.accounts.node_registry,
+        &mut ctx.accounts.registry_page,
+        &mut ctx.accounts.provider_node,
+        page_index,
+        page_bump,
+    )
+}
+
+/// Places `provider_node` on page `page_index`. Free slots on existing pages must be used before
+/// a new page (`page_index == page_count`) is allocated.
+pub fn add_to_registry(
+    node_registry: &mut NodeRegistry,
+    registry_page: &mut RegistryPage,
+    provider_node: &mut ProviderNode,
+    page_index: u32,
+    page_bump: u8,
+) -> Result<()> {
+    require!(provider_node.registry_page.is_none(), ErrorCode::NodeAlreadyRegistered);
+    require!(page_index <= node_registry.page_count, ErrorCode::InvalidRegistryPage);
+
+    if page_index == node_registry.page_count {
+        require!(node_registry.free_slots() == 0, ErrorCode::InvalidRegistryPage);
+        registry_page.bump = page_bump;
+        registry_page.index = page_index;
+        node_registry.page_count += 1;
+    }
+
+    require!(registry_page.insert(provider_node.owner), ErrorCode::RegistryPageFull);
+    node_registry.node_count += 1;
+    provider_node.registry_page = Some(page_index);
+
+    msg!(
+        "ProviderNode {} registered on page {}. Total nodes: {}",
+        provider_node.owner,
+        page_index,
+        node_registry.node_count
+    );
+    Ok(())
+}
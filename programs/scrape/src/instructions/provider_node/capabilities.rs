@@ -0,0 +1,28 @@
+use crate::state::capability::NodeCapabilities;
+use crate::state::provider_node::ProviderNode;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetNodeCapabilitiesContext<'info> {
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), signer.key().as_ref()],
+        bump,
+        constraint = provider_node.owner == signer.key() @ ErrorCode::UnauthorizedNode
+    )]
+    pub provider_node: Box<Account<'info, ProviderNode>>,
+    pub signer: Signer<'info>,
+}
+
+/// Publishes a new capability profile. Tasks already assigned are unaffected.
+#[inline(never)]
+pub fn set_capabilities(ctx: Context<SetNodeCapabilitiesContext>, capabilities: NodeCapabilities) -> Result<()> {
+    require!(capabilities.is_valid(), ErrorCode::InvalidCapabilities);
+
+    let provider_node = &mut ctx.accounts.provider_node;
+    provider_node.capabilities = capabilities;
+
+    msg!("ProviderNode {} capabilities updated: {:?}", provider_node.owner, provider_node.capabilities);
+    Ok(())
+}
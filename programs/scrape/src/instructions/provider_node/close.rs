@@ -0,0 +1,89 @@
+use crate::error::ErrorCode;
+use crate::instructions::node_registry::remove_from_registry;
+use crate::state::node_registry::{NodeRegistry, RegistryPage};
+use crate::state::provider_node::ProviderNode;
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct CloseProviderNodeContext<'info> {
+    #[account(
+        mut,
+        close = signer,
+        seeds = [ProviderNode::PREFIX.as_bytes(), signer.key().as_ref()],
+        bump,
+        constraint = provider_node.owner == signer.key() @ ErrorCode::UnauthorizedNode
+    )]
+    pub provider_node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        mut,
+        seeds = [ProviderNode::STAKE_PREFIX.as_bytes(), provider_node.key().as_ref()],
+        bump
+    )]
+    pub stake_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [TokenVault::PREFIX.as_bytes(), token_vault.mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(
+        mut,
+        seeds = [NodeRegistry::PREFIX.as_bytes()],
+        bump = node_registry.bump
+    )]
+    pub node_registry: Box<Account<'info, NodeRegistry>>,
+    #[account(
+        mut,
+        seeds = [
+            RegistryPage::PREFIX.as_bytes(),
+            provider_node.registry_page.unwrap_or_default().to_le_bytes().as_ref()
+        ],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Option<Box<Account<'info, RegistryPage>>>, // Required while the node is registered
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Closes a node with no in-flight tasks and no remaining stake, removing it from the registry
+/// and refunding the rent of both the node and its stake account to the owner.
+#[inline(never)]
+pub fn close(ctx: Context<CloseProviderNodeContext>) -> Result<()> {
+    let provider_node = &mut ctx.accounts.provider_node;
+
+    require!(provider_node.active_tasks == 0, ErrorCode::NodeHasActiveTasks);
+    require!(
+        provider_node.stake == 0
+            && provider_node.unstake_amount == 0
+            && ctx.accounts.stake_token_account.amount == 0,
+        ErrorCode::StakeNotWithdrawn
+    );
+
+    if provider_node.registry_page.is_some() {
+        let registry_page = ctx
+            .accounts
+            .registry_page
+            .as_mut()
+            .ok_or(ErrorCode::NodeNotRegistered)?;
+        remove_from_registry(&mut ctx.accounts.node_registry, registry_page, provider_node)?;
+    }
+
+    let token_vault = &ctx.accounts.token_vault;
+    let mint = token_vault.mint;
+    let bump = [token_vault.bump];
+    let seeds: &[&[u8]] = &[TokenVault::PREFIX.as_bytes(), mint.as_ref(), &bump];
+    let signer_seeds = &[seeds];
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.stake_token_account.to_account_info(),
+        destination: ctx.accounts.signer.to_account_info(),
+        authority: token_vault.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token_interface::close_account(cpi_ctx)?;
+
+    msg!("ProviderNode closed: {}", provider_node.owner);
+    Ok(())
+}
@@ -1,8 +1,14 @@
-use crate::state::{provider_node::ProviderNode, node_registry::NodeRegistry};
+use crate::error::ErrorCode;
+use crate::instructions::node_registry::add_to_registry;
+use crate::state::{provider_node::ProviderNode, node_registry::{NodeRegistry, RegistryPage}, token::TokenVault};
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
+use crate::state::config::Config;
+use crate::state::capability::NodeCapabilities;
 
 #[derive(Accounts)]
+#[instruction(connection_commitment: [u8; 32], bandwidth_limit: u64, stake_amount: u64, capabilities: NodeCapabilities, page_index: u32)]
 pub struct CreateProviderNodeContext<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -17,40 +23,88 @@ pub struct CreateProviderNodeContext<'info> {
     #[account(
         mut,
         seeds = [NodeRegistry::PREFIX.as_bytes()],
-        bump = node_registry.bump,
-        realloc = NodeRegistry::calculate_size(node_registry.nodes.len() + 1),
-        realloc::payer = signer,
-        realloc::zero = false
+        bump = node_registry.bump
     )]
     pub node_registry: Box<Account<'info, NodeRegistry>>,
     #[account(
-        constraint = node_token_account.mint == crate::SCRAPE_MINT.parse::<Pubkey>().unwrap(),
+        init_if_needed,
+        payer = signer,
+        space = RegistryPage::SIZE,
+        seeds = [RegistryPage::PREFIX.as_bytes(), page_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub registry_page: Box<Account<'info, RegistryPage>>,
+    #[account(
+        mut,
+        constraint = node_token_account.mint == config.params.scrape_mint,
         constraint = node_token_account.owner == signer.key(),
     )]
-    pub node_token_account: Box<Account<'info, TokenAccount>>,
+    pub node_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = signer,
+        seeds = [ProviderNode::STAKE_PREFIX.as_bytes(), provider_node.key().as_ref()],
+        bump,
+        token::mint = scrape_mint,
+        token::authority = token_vault,
+        token::token_program = token_program,
+    )]
+    pub stake_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [TokenVault::PREFIX.as_bytes(), scrape_mint.key().as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(
+        constraint = scrape_mint.key() == config.params.scrape_mint,
+    )]
+    pub scrape_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
     pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 #[inline(never)]
 pub fn create(
     ctx: Context<CreateProviderNodeContext>,
-    ipv4: [u8; 4],
-    proxy_port: u16,
-    client_port: u16,
+    connection_commitment: [u8; 32],
     bandwidth_limit: u64,
+    stake_amount: u64,
+    capabilities: NodeCapabilities,
+    page_index: u32,
 ) -> Result<()> {
+    // Registration requires collateral so that spinning up nodes is not free
+    require!(capabilities.is_valid(), ErrorCode::InvalidCapabilities);
+    require!(connection_commitment != [0; 32], ErrorCode::InvalidConnectionCommitment);
+
+    let stake = transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.node_token_account,
+        &mut ctx.accounts.stake_token_account,
+        &ctx.accounts.scrape_mint,
+        ctx.accounts.signer.to_account_info(),
+        &[],
+        stake_amount,
+    )?;
+    // The minimum applies to what was staked after any transfer fee
+    require!(stake >= ctx.accounts.config.params.min_stake, ErrorCode::InsufficientStake);
+
     let signer = &ctx.accounts.signer;
     let provider_node = &mut ctx.accounts.provider_node;
     let node_token_account = &ctx.accounts.node_token_account;
-    let node_registry = &mut ctx.accounts.node_registry;
 
     // Initialize the ProviderNode
     provider_node.bump = ctx.bumps.provider_node;
     provider_node.owner = signer.key();
-    provider_node.ipv4 = ipv4;
-    provider_node.proxy_port = proxy_port;
-    provider_node.client_port = client_port;
+    provider_node.legacy_ipv4 = [0; 4];
+    provider_node.legacy_proxy_port = 0;
+    provider_node.legacy_client_port = 0;
+    provider_node.connection_commitment = connection_commitment;
     provider_node.bandwidth_limit = bandwidth_limit;
     provider_node.bandwidth_used = 0;
     provider_node.reputation = 0;
@@ -58,14 +112,31 @@ pub fn create(
     provider_node.active = true;
     provider_node.token_account = node_token_account.key();
     provider_node.last_bonus_claim = 0;
+    provider_node.strikes = 0;
+    provider_node.stake_account = ctx.accounts.stake_token_account.key();
+    provider_node.stake = stake;
+    provider_node.unstake_amount = 0;
+    provider_node.unstake_requested_at = 0;
+    provider_node.reported_bandwidth = 0;
+    provider_node.reputation_updated_at = Clock::get()?.unix_timestamp;
+    provider_node.tasks_accepted = 0;
+    provider_node.tasks_rejected = 0;
+    provider_node.disputes_lost = 0;
+    provider_node.session_key = None;
+    provider_node.session_expires_at = 0;
+    provider_node.registry_page = None;
+    provider_node.active_tasks = 0;
+    provider_node.capabilities = capabilities;
 
-    // Add the provider node's owner to the NodeRegistry
-    if !node_registry.nodes.contains(&provider_node.owner) {
-        node_registry.nodes.push(provider_node.owner);
-    }
-
-    msg!("ProviderNode created for user: {}", provider_node.owner);
-    msg!("ProviderNode added to NodeRegistry. Total nodes: {}", node_registry.nodes.len());
+    msg!("ProviderNode created for user: {}. Staked: {} $SCRAPE", provider_node.owner, stake);
 
-    Ok(())
+    // List the node in the NodeRegistry so it can be selected for tasks
+    let page_bump = ctx.bumps.registry_page;
+    add_to_registry(
+        &mut ctx.accounts.node_registry,
+        &mut ctx.accounts.registry_page,
+        provider_node,
+        page_index,
+        page_bump,
+    )
 }
\ No newline at end of file
@@ -0,0 +1,99 @@
+use crate::error::ErrorCode;
+use crate::state::config::Config;
+use crate::state::provider_node::ProviderNode;
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+pub struct MigrateProviderNodeContext<'info> {
+    /// CHECK: older layouts are too short to deserialize; the discriminator is checked and the
+    /// account is resized before it is read
+    #[account(
+        mut,
+        owner = crate::ID @ ErrorCode::InvalidMigration,
+        seeds = [ProviderNode::PREFIX.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub provider_node: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        seeds = [ProviderNode::STAKE_PREFIX.as_bytes(), provider_node.key().as_ref()],
+        bump,
+        token::mint = scrape_mint,
+        token::authority = token_vault,
+        token::token_program = token_program,
+    )]
+    pub stake_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [TokenVault::PREFIX.as_bytes(), scrape_mint.key().as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = config.params.scrape_mint)]
+    pub scrape_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Brings a ProviderNode created under an older layout up to date: grows the account to the
+/// current size (the owner pays the extra rent; new fields start zeroed), erases the legacy
+/// plaintext IP and ports and opens the stake account. A node staking less than `min_stake` is
+/// paused. The node must then publish a connection commitment with `update_node`, and stake with
+/// `increase_stake` and `resume_node` if paused, before it can take tasks. Safe to call on an
+/// up-to-date account.
+#[inline(never)]
+pub fn migrate(ctx: Context<MigrateProviderNodeContext>) -> Result<()> {
+    let info = ctx.accounts.provider_node.to_account_info();
+    require!(
+        info.try_borrow_data()?.starts_with(ProviderNode::DISCRIMINATOR),
+        ErrorCode::InvalidMigration
+    );
+
+    if info.data_len() < ProviderNode::SIZE {
+        let required = Rent::get()?.minimum_balance(ProviderNode::SIZE);
+        let top_up = required.saturating_sub(info.lamports());
+        if top_up > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.signer.to_account_info(),
+                to: info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            transfer(cpi_ctx, top_up)?;
+        }
+        info.realloc(ProviderNode::SIZE, true)?;
+    }
+
+    let mut provider_node = ProviderNode::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require!(provider_node.owner == ctx.accounts.signer.key(), ErrorCode::InvalidMigration);
+
+    provider_node.legacy_ipv4 = [0; 4];
+    provider_node.legacy_proxy_port = 0;
+    provider_node.legacy_client_port = 0;
+
+    // Fields added since the account was created read as zero; start decay from now
+    if provider_node.reputation_updated_at == 0 {
+        provider_node.reputation_updated_at = Clock::get()?.unix_timestamp;
+    }
+
+    // Legacy nodes never staked: keep them out of assignment and selection until they do
+    provider_node.stake_account = ctx.accounts.stake_token_account.key();
+    if !provider_node.has_min_stake(ctx.accounts.config.params.min_stake) {
+        provider_node.active = false;
+    }
+
+    provider_node.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    msg!("ProviderNode migrated: {}", info.key());
+    Ok(())
+}
@@ -1,7 +1,17 @@
+pub mod capabilities;
 pub mod create;
+pub mod migrate;
+pub mod close;
+pub mod session;
+pub mod status;
 pub mod update;
 pub mod update_report;
 
+pub use capabilities::*;
 pub use create::*;
+pub use migrate::*;
+pub use close::*;
+pub use session::*;
+pub use status::*;
 pub use update::*;
-pub use update_report::*;
\ No newline at end of file
+pub use update_report::*;
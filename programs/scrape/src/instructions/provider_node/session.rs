@@ -0,0 +1,43 @@
+use crate::state::provider_node::ProviderNode;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetSessionKeyContext<'info> {
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), signer.key().as_ref()],
+        bump,
+        constraint = provider_node.owner == signer.key() @ ErrorCode::UnauthorizedNode
+    )]
+    pub provider_node: Box<Account<'info, ProviderNode>>,
+    pub signer: Signer<'info>,
+}
+
+/// Delegates task completion to a hot `session_key` until `expires_at`. Passing `None` revokes it.
+#[inline(never)]
+pub fn set_session_key(
+    ctx: Context<SetSessionKeyContext>,
+    session_key: Option<Pubkey>,
+    expires_at: i64,
+) -> Result<()> {
+    let provider_node = &mut ctx.accounts.provider_node;
+
+    match session_key {
+        Some(key) => {
+            let now = Clock::get()?.unix_timestamp;
+            require!(expires_at > now, ErrorCode::InvalidSessionKey);
+            require!(key != provider_node.owner, ErrorCode::InvalidSessionKey);
+
+            provider_node.session_key = Some(key);
+            provider_node.session_expires_at = expires_at;
+            msg!("ProviderNode {} session key set: {} until {}", provider_node.key(), key, expires_at);
+        }
+        None => {
+            provider_node.session_key = None;
+            provider_node.session_expires_at = 0;
+            msg!("ProviderNode {} session key revoked", provider_node.key());
+        }
+    }
+    Ok(())
+}
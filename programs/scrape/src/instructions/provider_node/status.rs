@@ -0,0 +1,46 @@
+use crate::state::provider_node::ProviderNode;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct SetNodeStatusContext<'info> {
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), signer.key().as_ref()],
+        bump,
+        constraint = provider_node.owner == signer.key() @ ErrorCode::UnauthorizedNode
+    )]
+    pub provider_node: Box<Account<'info, ProviderNode>>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+/// Takes the node offline. Reputation and stake are untouched and tasks already assigned can
+/// still be completed.
+#[inline(never)]
+pub fn pause(ctx: Context<SetNodeStatusContext>) -> Result<()> {
+    let provider_node = &mut ctx.accounts.provider_node;
+    provider_node.active = false;
+
+    msg!("ProviderNode paused: {}", provider_node.owner);
+    Ok(())
+}
+
+/// Brings the node back online, provided its stake net of any pending unstake meets `min_stake`.
+#[inline(never)]
+pub fn resume(ctx: Context<SetNodeStatusContext>) -> Result<()> {
+    let provider_node = &mut ctx.accounts.provider_node;
+
+    let bonded = provider_node.stake.saturating_sub(provider_node.unstake_amount);
+    require!(bonded >= ctx.accounts.config.params.min_stake, ErrorCode::InsufficientStake);
+
+    provider_node.active = true;
+
+    msg!("ProviderNode resumed: {}", provider_node.owner);
+    Ok(())
+}
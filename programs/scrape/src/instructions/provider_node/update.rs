@@ -17,9 +17,7 @@ pub struct UpdateProviderNodeContext<'info> {
 #[inline(never)]
 pub fn update(
     ctx: Context<UpdateProviderNodeContext>,
-    ipv4: [u8; 4],
-    proxy_port: u16,
-    client_port: u16,
+    connection_commitment: [u8; 32],
     bandwidth_limit: u64,
 ) -> Result<()> {
     let provider_node = &mut ctx.accounts.provider_node;
@@ -27,11 +25,13 @@ pub fn update(
 
     // Verify the signer is the owner of the ProviderNode
     require!(provider_node.owner == signer.key(), ErrorCode::UnauthorizedNode);
+    require!(connection_commitment != [0; 32], ErrorCode::InvalidConnectionCommitment);
 
     // Update fields
-    provider_node.ipv4 = ipv4;
-    provider_node.proxy_port = proxy_port;
-    provider_node.client_port = client_port;
+    provider_node.legacy_ipv4 = [0; 4];
+    provider_node.legacy_proxy_port = 0;
+    provider_node.legacy_client_port = 0;
+    provider_node.connection_commitment = connection_commitment;
     provider_node.bandwidth_limit = bandwidth_limit;
 
     msg!("ProviderNode updated: {}", provider_node.key());
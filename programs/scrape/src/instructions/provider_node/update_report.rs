@@ -1,6 +1,5 @@
 use crate::state::provider_node::ProviderNode;
 use anchor_lang::prelude::*;
-use crate::error::ErrorCode;
 
 #[derive(Accounts)]
 pub struct UpdateProviderNodeReportContext<'info> {
@@ -14,24 +13,20 @@ pub struct UpdateProviderNodeReportContext<'info> {
     pub signer: Signer<'info>,
 }
 
+/// Records self-reported telemetry only; reputation and rewards are driven by program events.
 #[inline(never)]
 pub fn update_report(
     ctx: Context<UpdateProviderNodeReportContext>,
     bandwidth_used: u64,
-    reputation_increase: u64,
 ) -> Result<()> {
     let provider_node = &mut ctx.accounts.provider_node;
-    let signer = &ctx.accounts.signer;
 
-    // Update usage and reputation
-    provider_node.bandwidth_used += bandwidth_used;
-    provider_node.reputation += reputation_increase;
+    provider_node.reported_bandwidth = provider_node.reported_bandwidth.saturating_add(bandwidth_used);
 
     msg!(
-        "ProviderNode report updated: {}. Bandwidth Used: {} MB, Reputation: {}",
+        "ProviderNode report updated: {}. Reported Bandwidth: {} MB",
         provider_node.key(),
-        provider_node.bandwidth_used,
-        provider_node.reputation
+        provider_node.reported_bandwidth
     );
     Ok(())
-}
\ No newline at end of file
+}
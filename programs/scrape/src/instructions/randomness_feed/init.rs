@@ -0,0 +1,29 @@
+use crate::state::randomness::RandomnessFeed;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeRandomnessFeedContext<'info> {
+    #[account(
+        init,
+        payer = signer,
+        space = RandomnessFeed::SIZE,
+        seeds = [RandomnessFeed::PREFIX.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub randomness_feed: Box<Account<'info, RandomnessFeed>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init(ctx: Context<InitializeRandomnessFeedContext>) -> Result<()> {
+    let randomness_feed = &mut ctx.accounts.randomness_feed;
+    randomness_feed.bump = ctx.bumps.randomness_feed;
+    randomness_feed.authority = ctx.accounts.signer.key();
+    randomness_feed.value = [0; 32];
+    randomness_feed.slot = 0;
+    randomness_feed.round = 0;
+
+    msg!("Randomness feed initialized: {}", randomness_feed.key());
+    Ok(())
+}
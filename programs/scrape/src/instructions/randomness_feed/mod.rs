@@ -0,0 +1,5 @@
+pub mod init;
+pub mod publish;
+
+pub use init::*;
+pub use publish::*;
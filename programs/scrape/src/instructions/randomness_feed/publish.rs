@@ -0,0 +1,26 @@
+use crate::error::ErrorCode;
+use crate::state::randomness::RandomnessFeed;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PublishRandomnessContext<'info> {
+    #[account(
+        mut,
+        seeds = [RandomnessFeed::PREFIX.as_bytes(), authority.key().as_ref()],
+        bump = randomness_feed.bump,
+        has_one = authority @ ErrorCode::UnauthorizedNode
+    )]
+    pub randomness_feed: Box<Account<'info, RandomnessFeed>>,
+    pub authority: Signer<'info>,
+}
+
+#[inline(never)]
+pub fn publish(ctx: Context<PublishRandomnessContext>, value: [u8; 32]) -> Result<()> {
+    let randomness_feed = &mut ctx.accounts.randomness_feed;
+    randomness_feed.value = value;
+    randomness_feed.slot = Clock::get()?.slot;
+    randomness_feed.round = randomness_feed.round.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Randomness round {} published at slot {}", randomness_feed.round, randomness_feed.slot);
+    Ok(())
+}
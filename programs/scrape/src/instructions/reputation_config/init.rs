@@ -0,0 +1,37 @@
+use crate::error::ErrorCode;
+use crate::state::reputation::{ReputationConfig, ReputationWeights};
+use crate::program::Scrape;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeReputationConfigContext<'info> {
+    #[account(
+        init,
+        payer = signer,
+        space = ReputationConfig::SIZE,
+        seeds = [ReputationConfig::PREFIX.as_bytes()],
+        bump
+    )]
+    pub reputation_config: Box<Account<'info, ReputationConfig>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Scrape>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(signer.key()) @ ErrorCode::UnauthorizedDeployer)]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the singleton reputation config. Restricted to the program upgrade authority, like the
+/// main config.
+pub fn init(ctx: Context<InitializeReputationConfigContext>, weights: ReputationWeights) -> Result<()> {
+    require!(weights.half_life > 0, ErrorCode::InvalidHalfLife);
+
+    let reputation_config = &mut ctx.accounts.reputation_config;
+    reputation_config.bump = ctx.bumps.reputation_config;
+    reputation_config.authority = ctx.accounts.signer.key();
+    reputation_config.weights = weights;
+
+    msg!("Reputation config initialized: {}", reputation_config.key());
+    Ok(())
+}
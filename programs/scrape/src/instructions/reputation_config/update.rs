@@ -0,0 +1,26 @@
+use crate::error::ErrorCode;
+use crate::state::reputation::{ReputationConfig, ReputationWeights};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateReputationConfigContext<'info> {
+    #[account(
+        mut,
+        seeds = [ReputationConfig::PREFIX.as_bytes()],
+        bump = reputation_config.bump,
+        has_one = authority @ ErrorCode::UnauthorizedNode
+    )]
+    pub reputation_config: Box<Account<'info, ReputationConfig>>,
+    pub authority: Signer<'info>,
+}
+
+#[inline(never)]
+pub fn update(ctx: Context<UpdateReputationConfigContext>, weights: ReputationWeights) -> Result<()> {
+    require!(weights.half_life > 0, ErrorCode::InvalidHalfLife);
+
+    let reputation_config = &mut ctx.accounts.reputation_config;
+    reputation_config.weights = weights;
+
+    msg!("Reputation config updated: {:?}", reputation_config.weights);
+    Ok(())
+}
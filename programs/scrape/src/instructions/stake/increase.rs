@@ -0,0 +1,60 @@
+use crate::state::provider_node::ProviderNode;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
+use crate::error::ErrorCode;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct IncreaseStakeContext<'info> {
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub provider_node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        mut,
+        seeds = [ProviderNode::STAKE_PREFIX.as_bytes(), provider_node.key().as_ref()],
+        bump
+    )]
+    pub stake_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = config.params.scrape_mint)]
+    pub scrape_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = node_token_account.mint == config.params.scrape_mint,
+        constraint = node_token_account.owner == signer.key(),
+    )]
+    pub node_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[inline(never)]
+pub fn increase(ctx: Context<IncreaseStakeContext>, amount: u64) -> Result<()> {
+    // Only what arrives after any transfer fee counts as stake
+    let received = transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.node_token_account,
+        &mut ctx.accounts.stake_token_account,
+        &ctx.accounts.scrape_mint,
+        ctx.accounts.signer.to_account_info(),
+        &[],
+        amount,
+    )?;
+
+    let provider_node = &mut ctx.accounts.provider_node;
+    provider_node.stake = provider_node
+        .stake
+        .checked_add(received)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Stake increased for node: {}. Staked: {} $SCRAPE", provider_node.owner, provider_node.stake);
+    Ok(())
+}
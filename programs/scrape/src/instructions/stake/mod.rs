@@ -0,0 +1,7 @@
+pub mod increase;
+pub mod unstake;
+pub mod slash;
+
+pub use increase::*;
+pub use unstake::*;
+pub use slash::*;
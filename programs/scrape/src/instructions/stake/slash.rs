@@ -0,0 +1,55 @@
+use crate::state::provider_node::ProviderNode;
+use crate::state::config::ConfigParams;
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
+use crate::error::ErrorCode;
+
+/// Moves `slash_bps` of the node's stake into the $SCRAPE vault's treasury and deactivates the node if it drops below the configured `min_stake`.
+/// Shared by stalled-task reclaim and dispute settlement; returns the slashed amount.
+#[allow(clippy::too_many_arguments)]
+pub fn slash_stake<'info>(
+    provider_node: &mut Account<'info, ProviderNode>,
+    stake_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_vault: &mut Account<'info, TokenVault>,
+    scrape_mint: &InterfaceAccount<'info, Mint>,
+    vault_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    params: &ConfigParams,
+    slash_bps: u16,
+) -> Result<u64> {
+    let amount = (provider_node.stake as u128)
+        .checked_mul(slash_bps as u128)
+        .and_then(|amount| amount.checked_div(crate::BPS_DENOMINATOR as u128))
+        .ok_or(ErrorCode::MathOverflow)? as u64;
+
+    if amount > 0 {
+        let mint = token_vault.mint;
+        let bump = [token_vault.bump];
+        let seeds: &[&[u8]] = &[TokenVault::PREFIX.as_bytes(), mint.as_ref(), &bump];
+        let received = transfer_tokens(
+            token_program,
+            stake_token_account,
+            vault_token_account,
+            scrape_mint,
+            token_vault.to_account_info(),
+            &[seeds],
+            amount,
+        )?;
+
+        provider_node.stake -= amount;
+        token_vault.total_slashed = token_vault
+            .total_slashed
+            .checked_add(received)
+            .ok_or(ErrorCode::MathOverflow)?;
+        token_vault.credit_treasury(received)?;
+    }
+
+    if provider_node.stake.saturating_sub(provider_node.unstake_amount) < params.min_stake {
+        provider_node.active = false;
+    }
+
+    msg!("Slashed {} $SCRAPE from node: {}. Remaining stake: {}", amount, provider_node.owner, provider_node.stake);
+    Ok(amount)
+}
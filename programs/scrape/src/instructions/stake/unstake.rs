@@ -0,0 +1,126 @@
+use crate::state::provider_node::ProviderNode;
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
+use crate::error::ErrorCode;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct RequestUnstakeContext<'info> {
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub provider_node: Box<Account<'info, ProviderNode>>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+/// Starts the cooldown for `amount`; a node left below the configured `min_stake` stops taking work.
+#[inline(never)]
+pub fn request_unstake(ctx: Context<RequestUnstakeContext>, amount: u64) -> Result<()> {
+    let provider_node = &mut ctx.accounts.provider_node;
+
+    require!(amount > 0 && amount <= provider_node.stake, ErrorCode::InsufficientStake);
+    // Stake backing assigned or submitted tasks stays slashable
+    require!(provider_node.active_tasks == 0, ErrorCode::NodeHasActiveTasks);
+
+    provider_node.unstake_amount = amount;
+    provider_node.unstake_requested_at = Clock::get()?.unix_timestamp;
+
+    if provider_node.stake - amount < ctx.accounts.config.params.min_stake {
+        provider_node.active = false;
+    }
+
+    msg!(
+        "Unstake of {} $SCRAPE requested for node: {}. Active: {}",
+        amount,
+        provider_node.owner,
+        provider_node.active
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStakeContext<'info> {
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub provider_node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        mut,
+        seeds = [ProviderNode::STAKE_PREFIX.as_bytes(), provider_node.key().as_ref()],
+        bump
+    )]
+    pub stake_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [TokenVault::PREFIX.as_bytes(), config.params.scrape_mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = config.params.scrape_mint)]
+    pub scrape_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = node_token_account.mint == config.params.scrape_mint,
+        constraint = node_token_account.owner == signer.key(),
+    )]
+    pub node_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[inline(never)]
+pub fn withdraw(ctx: Context<WithdrawStakeContext>) -> Result<()> {
+    let provider_node = &mut ctx.accounts.provider_node;
+    let token_vault = &ctx.accounts.token_vault;
+
+    require!(provider_node.unstake_amount > 0, ErrorCode::UnstakeNotRequested);
+    require!(provider_node.active_tasks == 0, ErrorCode::NodeHasActiveTasks);
+    let unlocks_at = provider_node
+        .unstake_requested_at
+        .checked_add(ctx.accounts.config.params.unstake_cooldown)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(Clock::get()?.unix_timestamp >= unlocks_at, ErrorCode::UnstakeCooldownActive);
+
+    // Slashing during the cooldown may have left less than was requested
+    let amount = provider_node.unstake_amount.min(provider_node.stake);
+
+    let mint = token_vault.mint;
+    let bump = [token_vault.bump];
+    let seeds: &[&[u8]] = &[TokenVault::PREFIX.as_bytes(), mint.as_ref(), &bump];
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.stake_token_account,
+        &mut ctx.accounts.node_token_account,
+        &ctx.accounts.scrape_mint,
+        token_vault.to_account_info(),
+        &[seeds],
+        amount,
+    )?;
+
+    provider_node.stake -= amount;
+    provider_node.unstake_amount = 0;
+    provider_node.unstake_requested_at = 0;
+
+    msg!(
+        "Withdrew {} $SCRAPE stake for node: {}. Remaining stake: {}",
+        amount,
+        provider_node.owner,
+        provider_node.stake
+    );
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::error::ErrorCode;
+use crate::state::config::Config;
 use crate::state::endpoint_node::EndpointNode;
-use crate::state::{provider_node::ProviderNode, task::{Task, TaskStatus}, node_registry::NodeRegistry};
+use crate::state::{provider_node::ProviderNode, task::{AssignerRole, Task, TaskStatus}, node_registry::NodeRegistry};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -12,17 +13,39 @@ pub struct AssignTaskContext<'info> {
     )]
     pub task: Box<Account<'info, Task>>,
     #[account(
+        mut,
         seeds = [ProviderNode::PREFIX.as_bytes(), node.owner.as_ref()],
         bump
     )]
     pub node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        address = task.endpoint_node @ ErrorCode::UnauthorizedNode
+    )]
+    pub endpoint_node: Box<Account<'info, EndpointNode>>,
     pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
 }
 
+/// Assigns a pending task. Only the task owner, the task's endpoint node owner, or, when the task
+/// allows self-claiming, the provider node itself may do so. The owner and endpoint are bound to
+/// the node drawn by `select_provider`.
 #[inline(never)]
 pub fn assign(ctx: Context<AssignTaskContext>) -> Result<()> {
     let task = &mut ctx.accounts.task;
-    let node = &ctx.accounts.node;
+    let node = &mut ctx.accounts.node;
+    let signer = ctx.accounts.signer.key();
+
+    let role = task
+        .assigner_role(&signer, &ctx.accounts.endpoint_node.owner, &node.owner)
+        .ok_or(ErrorCode::UnauthorizedAssigner)?;
+    require!(
+        role == AssignerRole::SelfClaim || task.selected_node == Some(node.owner),
+        ErrorCode::ProviderNotSelected
+    );
 
     // Verify task is in Pending state
     require!(
@@ -30,14 +53,24 @@ pub fn assign(ctx: Context<AssignTaskContext>) -> Result<()> {
         ErrorCode::TaskNotAssigned
     );
 
-    // Verify node is active
+    // Verify node is active and suited to the task
     require!(node.active, ErrorCode::InactiveNode);
+    require!(node.has_min_stake(ctx.accounts.config.params.min_stake), ErrorCode::InsufficientStake);
+    require!(node.can_take(&task.requirements), ErrorCode::RequirementsNotMet);
+
+    // Expired tasks can only be refunded
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= task.deadline, ErrorCode::TaskExpired);
+    require!(node.bandwidth_headroom(now) > 0, ErrorCode::BandwidthExceeded);
 
     // Assign task to node
     task.node_assigned = Some(node.owner);
     task.status = TaskStatus::Assigned;
+    task.assigned_at = now;
+    task.assigned_by = Some(signer);
+    node.active_tasks = node.active_tasks.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
 
-    msg!("Task {} assigned to node: {}", task.id, node.owner);
+    msg!("Task {} assigned to node: {} by {} ({:?})", task.id, node.owner, signer, role);
     Ok(())
 }
 
@@ -48,7 +81,8 @@ pub struct AssignTaskByEndpointContext<'info> {
         seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
         bump,
         has_one = endpoint_node @ ErrorCode::UnauthorizedNode,
-        constraint = task.status == TaskStatus::Pending @ ErrorCode::TaskNotAssigned
+        constraint = task.status == TaskStatus::Pending @ ErrorCode::TaskNotAssigned,
+        constraint = task.selected_node == Some(provider_node.owner) @ ErrorCode::ProviderNotSelected
     )]
     pub task: Box<Account<'info, Task>>,
     #[account(
@@ -65,20 +99,41 @@ pub struct AssignTaskByEndpointContext<'info> {
     )]
     pub node_registry: Box<Account<'info, NodeRegistry>>,
     #[account(
+        mut,
         seeds = [ProviderNode::PREFIX.as_bytes(), provider_node.owner.as_ref()],
         bump,
-        constraint = provider_node.active @ ErrorCode::InactiveNode
+        constraint = provider_node.active @ ErrorCode::InactiveNode,
+        constraint = provider_node.has_min_stake(config.params.min_stake) @ ErrorCode::InsufficientStake
     )]
     pub provider_node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
 }
 
 pub fn assign_task_by_endpoint(ctx: Context<AssignTaskByEndpointContext>) -> Result<()> {
     let task = &mut ctx.accounts.task;
-    let provider_node = &ctx.accounts.provider_node;
+    let provider_node = &mut ctx.accounts.provider_node;
+
+    // Expired tasks can only be refunded
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= task.deadline, ErrorCode::TaskExpired);
+
+    // The node may have changed its profile or filled up since it was drawn
+    require!(provider_node.can_take(&task.requirements), ErrorCode::RequirementsNotMet);
+    require!(provider_node.bandwidth_headroom(now) > 0, ErrorCode::BandwidthExceeded);
 
     // Assign the task to the provider node
     task.node_assigned = Some(provider_node.owner);
     task.status = TaskStatus::Assigned;
+    task.assigned_at = now;
+    task.assigned_by = Some(ctx.accounts.signer.key());
+    provider_node.active_tasks = provider_node
+        .active_tasks
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     msg!("Task {} assigned to provider node: {} by endpoint node: {}", 
         task.id, provider_node.owner, ctx.accounts.endpoint_node.key());
@@ -1,8 +1,7 @@
 use crate::state::provider_node::ProviderNode;
-use crate::state::task::{Task, /*TaskStatus*/};
+use crate::state::task::{DatasetManifest, Task};
 use crate::state::token::TokenVault;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
 use crate::error::ErrorCode;
 use crate::state::task::TaskStatus;
 #[derive(Accounts)]
@@ -21,28 +20,15 @@ pub struct CompleteTaskContext<'info> {
     pub node: Box<Account<'info, ProviderNode>>,
     #[account(
         mut,
-        seeds = [TokenVault::PREFIX.as_bytes()],
-        bump
+        seeds = [TokenVault::PREFIX.as_bytes(), task.payment_mint.as_ref()],
+        bump = token_vault.bump
     )]
     pub token_vault: Box<Account<'info, TokenVault>>,
-    #[account(
-        mut,
-        constraint = vault_token_account.mint == crate::SCRAPE_MINT.parse::<Pubkey>().unwrap(),
-        constraint = vault_token_account.owner == token_vault.key(),
-    )]
-    pub vault_token_account: Box<Account<'info, TokenAccount>>,
-    #[account(
-        mut,
-        constraint = node_token_account.mint == crate::SCRAPE_MINT.parse::<Pubkey>().unwrap(),
-        constraint = node_token_account.owner == node.owner,
-    )]
-    pub node_token_account: Box<Account<'info, TokenAccount>>,
     pub signer: Signer<'info>,
-    pub token_program: Program<'info, Token>,
 }
 
 #[inline(never)]
-pub fn complete(ctx: Context<CompleteTaskContext>, ipfs_hash: String) -> Result<()> {
+pub fn complete(ctx: Context<CompleteTaskContext>, manifest: DatasetManifest) -> Result<()> {
     let task = &mut ctx.accounts.task;
     let node = &mut ctx.accounts.node;
     let token_vault = &mut ctx.accounts.token_vault;
@@ -50,42 +36,56 @@ pub fn complete(ctx: Context<CompleteTaskContext>, ipfs_hash: String) -> Result<
     // Verify task is in Assigned state
     require!(task.status == TaskStatus::Assigned, ErrorCode::TaskNotAssigned);
 
-    // Verify node is the assigned node
+    // Verify node is the assigned node and the signer operates it
     require!(task.node_assigned == Some(node.owner), ErrorCode::UnauthorizedNode);
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        node.is_operator(&ctx.accounts.signer.key(), now),
+        ErrorCode::UnauthorizedNode
+    );
 
-    // Update task
-    task.status = TaskStatus::Completed;
-    task.ipfs_hash = Some(ipfs_hash.clone());
-    task.dataset_size = 1; // Placeholder: 1 MB (update with actual size in practice)
+    manifest.validate()?;
 
-    // Update node usage and reputation
-    node.bandwidth_used += task.dataset_size;
-    node.reputation += 10; // Increment reputation by 10 for completing a task
+    // The dataset has to fit in what the task paid for and in the node's hourly allowance
+    let dataset_size_mb = manifest.size_bytes.div_ceil(Task::BYTES_PER_MB);
+    require!(dataset_size_mb <= task.bandwidth_paid, ErrorCode::BandwidthExceeded);
+    require!(
+        dataset_size_mb <= node.bandwidth_headroom(now),
+        ErrorCode::BandwidthExceeded
+    );
+    let vault_used = token_vault
+        .bandwidth_used_mb
+        .checked_add(dataset_size_mb)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(vault_used <= token_vault.bandwidth_paid_mb, ErrorCode::BandwidthExceeded);
 
-    // Distribute rewards
-    let reward = task.reward;
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.vault_token_account.to_account_info(),
-        to: ctx.accounts.node_token_account.to_account_info(),
-        authority: token_vault.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    anchor_spl::token::transfer(cpi_ctx, reward)?;
+    // Submit the result for the owner's review; the escrow stays locked until it is accepted
+    task.status = TaskStatus::Submitted;
+    task.submitted_at = now;
+    task.dataset_size = manifest.size_bytes;
+    task.record_count = manifest.record_count;
+    task.content_hash = manifest.content_hash;
+    task.storage = manifest.storage;
+    task.dataset_uri = Some(manifest.uri);
+    task.sealed_connection = Vec::new();
 
-    // Update token vault
-    token_vault.total_rewards_distributed += reward;
-    token_vault.bandwidth_used += task.dataset_size;
+    // Update node and vault usage (MB)
+    node.record_bandwidth(dataset_size_mb, now)?;
+    token_vault.bandwidth_used_mb = vault_used;
+    token_vault.bytes_delivered = token_vault
+        .bytes_delivered
+        .checked_add(task.dataset_size)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     msg!(
-        "Task {} completed via proxy (IP: {:?}, Port: {}). IPFS Hash: {}. $SCRAPE Rewards: {} to node: {}. Reputation: {}",
+        "Task {} submitted by node {}. Dataset: {:?} {}, {} bytes, {} records. Awaiting review by owner: {}",
         task.id,
-        node.ipv4,
-        node.proxy_port,
-        ipfs_hash,
-        reward,
         node.owner,
-        node.reputation
+        task.storage,
+        task.dataset_uri.as_ref().unwrap(),
+        task.dataset_size,
+        task.record_count,
+        task.owner
     );
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,9 +1,11 @@
+use crate::state::capability::TaskRequirements;
 use crate::state::client::Client;
 use crate::state::endpoint_node::EndpointNode;
-use crate::state::task::{Task, TaskStatus};
+use crate::state::task::{StorageKind, Task, TaskStatus};
 use crate::state::token::TokenVault;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
 use crate::ErrorCode;
 
 #[derive(Accounts)]
@@ -31,28 +33,28 @@ pub struct CreateTaskContext<'info> {
     pub endpoint_node: Box<Account<'info, EndpointNode>>,
     #[account(
         mut,
-        seeds = [TokenVault::PREFIX.as_bytes()],
-        bump
+        seeds = [TokenVault::PREFIX.as_bytes(), payment_mint.key().as_ref()],
+        bump = token_vault.bump,
+        constraint = !token_vault.paused @ ErrorCode::VaultPaused,
+        constraint = token_vault.approved @ ErrorCode::MintNotApproved
     )]
     pub token_vault: Box<Account<'info, TokenVault>>,
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        constraint = vault_token_account.mint == crate::SCRAPE_MINT.parse::<Pubkey>().unwrap(),
-        constraint = vault_token_account.owner == token_vault.key(),
-    )]
-    pub vault_token_account: Box<Account<'info, TokenAccount>>,
-    #[account(
-        mut,
-        constraint = user_token_account.mint == crate::SCRAPE_MINT.parse::<Pubkey>().unwrap(),
+        constraint = user_token_account.mint == payment_mint.key(),
         constraint = user_token_account.owner == signer.key(),
     )]
-    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 #[inline(never)]
+#[allow(clippy::too_many_arguments)]
 pub fn create(
     ctx: Context<CreateTaskContext>,
     url: String,
@@ -60,6 +62,9 @@ pub fn create(
     label: String,
     format: String,
     reward: u64,
+    deadline: i64,
+    allow_self_claim: bool,
+    requirements: TaskRequirements,
 ) -> Result<()> {
     let signer = &ctx.accounts.signer;
     let client = &mut ctx.accounts.client;
@@ -67,19 +72,30 @@ pub fn create(
     let endpoint_node = &ctx.accounts.endpoint_node;
     let token_vault = &mut ctx.accounts.token_vault;
 
-    // Transfer reward tokens to the vault
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.user_token_account.to_account_info(),
-        to: ctx.accounts.vault_token_account.to_account_info(),
-        authority: signer.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    anchor_spl::token::transfer(cpi_ctx, reward)?;
+    // The deadline bounds how long the reward can sit unassigned in escrow
+    let now = Clock::get()?.unix_timestamp;
+    require!(deadline > now, ErrorCode::InvalidDeadline);
+    require!(requirements.is_valid(), ErrorCode::InvalidRequirements);
+
+    // Transfer reward tokens to the vault; only what arrives after any transfer fee is escrowed
+    let escrow = transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.user_token_account,
+        &mut ctx.accounts.vault_token_account,
+        &ctx.accounts.payment_mint,
+        signer.to_account_info(),
+        &[],
+        reward,
+    )?;
+
+    // The escrow buys the bandwidth the provider may spend on the task
+    let bandwidth_paid = escrow / token_vault.rate_per_mb;
+    require!(bandwidth_paid > 0, ErrorCode::InsufficientReward);
 
     // Increment task counter in client
     let task_id = client.task_counter;
-    client.task_counter += 1;
+    client.task_counter = client.task_counter.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    client.open_task()?;
 
     // Initialize task
     task.bump = ctx.bumps.task;
@@ -93,11 +109,35 @@ pub fn create(
     task.reward = reward;
     task.status = TaskStatus::Pending;
     task.node_assigned = None;
-    task.ipfs_hash = None;
+    task.dataset_uri = None;
     task.dataset_size = 0;
+    task.record_count = 0;
+    task.content_hash = [0; 32];
+    task.storage = StorageKind::default();
+    task.allow_self_claim = allow_self_claim;
+    task.assigned_by = None;
+    task.selected_node = None;
+    task.selection = None;
+    task.sealed_connection = Vec::new();
+    task.requirements = requirements;
+    task.escrow = escrow;
+    task.bandwidth_paid = bandwidth_paid;
+    task.payment_mint = ctx.accounts.payment_mint.key();
+    task.deadline = deadline;
+    task.created_at = now;
+    task.assigned_at = 0;
+    task.submitted_at = 0;
 
     // Update token vault
-    token_vault.bandwidth_paid += reward;
+    token_vault.bandwidth_paid_mb = token_vault
+        .bandwidth_paid_mb
+        .checked_add(bandwidth_paid)
+        .ok_or(ErrorCode::MathOverflow)?;
+    token_vault.lock_escrow(escrow)?;
+    token_vault.total_escrow_deposited = token_vault
+        .total_escrow_deposited
+        .checked_add(escrow)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     msg!("Task created: ID {}, URL: {}", task.id, task.url);
     Ok(())
@@ -110,7 +150,8 @@ pub struct CloseTaskContext<'info> {
         close = signer,
         seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
         bump,
-        constraint = task.owner == signer.key() @ ErrorCode::UnauthorizedNode
+        constraint = task.owner == signer.key() @ ErrorCode::UnauthorizedNode,
+        constraint = task.escrow == 0 @ ErrorCode::EscrowLocked
     )]
     pub task: Box<Account<'info, Task>>,
     #[account(mut)]
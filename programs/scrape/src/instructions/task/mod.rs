@@ -1,9 +1,17 @@
 pub mod create;
-pub mod reward;
 pub mod assign;
 pub mod complete;
+pub mod refund;
+pub mod reclaim;
+pub mod review;
+pub mod select;
+pub mod share;
 
 pub use create::*;
-pub use reward::*;
 pub use assign::*;
-pub use complete::*;
\ No newline at end of file
+pub use complete::*;
+pub use refund::*;
+pub use reclaim::*;
+pub use review::*;
+pub use select::*;
+pub use share::*;
\ No newline at end of file
@@ -0,0 +1,106 @@
+use crate::error::ErrorCode;
+use crate::instructions::stake::slash_stake;
+use crate::state::provider_node::ProviderNode;
+use crate::state::reputation::{ReputationConfig, ReputationEvent};
+use crate::state::task::{Task, TaskStatus};
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct ReclaimStalledTaskContext<'info> {
+    #[account(
+        mut,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = task.status == TaskStatus::Assigned @ ErrorCode::TaskNotAssigned,
+        constraint = task.node_assigned == Some(node.owner) @ ErrorCode::UnauthorizedNode
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), node.owner.as_ref()],
+        bump
+    )]
+    pub node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        seeds = [ReputationConfig::PREFIX.as_bytes()],
+        bump = reputation_config.bump
+    )]
+    pub reputation_config: Box<Account<'info, ReputationConfig>>,
+    #[account(
+        mut,
+        seeds = [ProviderNode::STAKE_PREFIX.as_bytes(), node.key().as_ref()],
+        bump
+    )]
+    pub stake_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), config.params.scrape_mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = config.params.scrape_mint)]
+    pub scrape_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless: once an assignment outlives the configured `assignment_timeout`, anyone can put the task back in the queue.
+#[inline(never)]
+pub fn reclaim_stalled(ctx: Context<ReclaimStalledTaskContext>) -> Result<()> {
+    let task = &mut ctx.accounts.task;
+    let node = &mut ctx.accounts.node;
+
+    let now = Clock::get()?.unix_timestamp;
+    let stalled_at = task
+        .assigned_at
+        .checked_add(ctx.accounts.config.params.assignment_timeout)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(now > stalled_at, ErrorCode::AssignmentNotStalled);
+
+    // Return the task to the queue
+    task.status = TaskStatus::Pending;
+    task.node_assigned = None;
+    task.assigned_at = 0;
+    task.assigned_by = None;
+    task.selected_node = None;
+    task.selection = None;
+    task.sealed_connection = Vec::new();
+
+    // Record the strike against the node that held it and slash its stake
+    node.strikes = node.strikes.saturating_add(1);
+    node.active_tasks = node.active_tasks.saturating_sub(1);
+    node.record_reputation_event(
+        &ctx.accounts.reputation_config.weights,
+        ReputationEvent::AssignmentStalled,
+        now,
+    );
+    slash_stake(
+        node,
+        &ctx.accounts.stake_token_account,
+        &mut ctx.accounts.token_vault,
+        &ctx.accounts.scrape_mint,
+        &mut ctx.accounts.vault_token_account,
+        &ctx.accounts.token_program,
+        &ctx.accounts.config.params,
+        ctx.accounts.config.params.stall_slash_bps,
+    )?;
+
+    msg!(
+        "Task {} reclaimed from stalled node: {}. Strikes: {}, Reputation: {}",
+        task.id,
+        node.owner,
+        node.strikes,
+        node.reputation
+    );
+    Ok(())
+}
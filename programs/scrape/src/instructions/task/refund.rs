@@ -0,0 +1,175 @@
+use crate::error::ErrorCode;
+use crate::state::task::{Task, TaskStatus};
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
+use crate::state::client::Client;
+
+#[derive(Accounts)]
+pub struct CancelTaskContext<'info> {
+    #[account(
+        mut,
+        close = signer,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = task.owner == signer.key() @ ErrorCode::UnauthorizedNode,
+        constraint = task.status == TaskStatus::Pending @ ErrorCode::TaskNotAssigned
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        mut,
+        seeds = [Client::PREFIX.as_bytes(), task.owner.as_ref()],
+        bump = client.bump
+    )]
+    pub client: Box<Account<'info, Client>>,
+    // Refunds stay open while the vault is paused: the breaker stops new deposits and payouts, not
+    // the return of escrow that is still locked for the owner
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), task.payment_mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = task.payment_mint)]
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = user_token_account.mint == task.payment_mint,
+        constraint = user_token_account.owner == signer.key(),
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[inline(never)]
+pub fn cancel(ctx: Context<CancelTaskContext>) -> Result<()> {
+    let refund = refund_escrow(
+        &mut ctx.accounts.task,
+        &mut ctx.accounts.client,
+        &mut ctx.accounts.token_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.vault_token_account,
+        &mut ctx.accounts.user_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    msg!(
+        "Task {} cancelled by owner: {}. Refunded {}",
+        ctx.accounts.task.id,
+        ctx.accounts.signer.key(),
+        refund
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundExpiredTaskContext<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        has_one = owner @ ErrorCode::UnauthorizedNode,
+        constraint = task.status == TaskStatus::Pending @ ErrorCode::TaskNotAssigned
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        mut,
+        seeds = [Client::PREFIX.as_bytes(), task.owner.as_ref()],
+        bump = client.bump
+    )]
+    pub client: Box<Account<'info, Client>>,
+    /// CHECK: Task owner receiving the rent refund, validated through `has_one` on the task
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    // Open while the vault is paused, see `CancelTaskContext`
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), task.payment_mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = task.payment_mint)]
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = user_token_account.mint == task.payment_mint,
+        constraint = user_token_account.owner == owner.key(),
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub signer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless: anyone may return the escrow of a task that was never picked up before its deadline.
+#[inline(never)]
+pub fn refund_expired(ctx: Context<RefundExpiredTaskContext>) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp > ctx.accounts.task.deadline,
+        ErrorCode::DeadlineNotReached
+    );
+
+    let refund = refund_escrow(
+        &mut ctx.accounts.task,
+        &mut ctx.accounts.client,
+        &mut ctx.accounts.token_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.vault_token_account,
+        &mut ctx.accounts.user_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    msg!(
+        "Task {} expired unassigned. Refunded {} to owner: {}",
+        ctx.accounts.task.id,
+        refund,
+        ctx.accounts.owner.key()
+    );
+    Ok(())
+}
+
+/// Moves the task's escrow from the vault back to the owner's token account.
+fn refund_escrow<'info>(
+    task: &mut Account<'info, Task>,
+    client: &mut Account<'info, Client>,
+    token_vault: &mut Account<'info, TokenVault>,
+    payment_mint: &InterfaceAccount<'info, Mint>,
+    vault_token_account: &InterfaceAccount<'info, TokenAccount>,
+    user_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<u64> {
+    let refund = task.escrow;
+
+    if refund > 0 {
+        token_vault.release_escrow(refund)?;
+
+        let mint = token_vault.mint;
+        let bump = [token_vault.bump];
+        let seeds: &[&[u8]] = &[TokenVault::PREFIX.as_bytes(), mint.as_ref(), &bump];
+        transfer_tokens(
+            token_program,
+            vault_token_account,
+            user_token_account,
+            payment_mint,
+            token_vault.to_account_info(),
+            &[seeds],
+            refund,
+        )?;
+
+        task.escrow = 0;
+    }
+
+    // The bandwidth the reward paid for was never used
+    token_vault.bandwidth_paid_mb = token_vault.bandwidth_paid_mb.saturating_sub(task.bandwidth_paid);
+    task.bandwidth_paid = 0;
+    client.close_task(0, false)?;
+
+    Ok(refund)
+}
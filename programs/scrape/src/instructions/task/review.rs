@@ -0,0 +1,437 @@
+use crate::error::ErrorCode;
+use crate::state::provider_node::ProviderNode;
+use crate::state::reputation::{ReputationConfig, ReputationEvent};
+use crate::state::task::{Task, TaskStatus};
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
+use crate::state::bonus::{record_contribution, BonusClaim, BonusEpoch};
+use crate::state::client::Client;
+use crate::state::config::{Config, ConfigParams};
+
+#[derive(Accounts)]
+pub struct AcceptResultContext<'info> {
+    #[account(
+        mut,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = task.owner == signer.key() @ ErrorCode::UnauthorizedNode,
+        constraint = task.status == TaskStatus::Submitted @ ErrorCode::ResultNotSubmitted,
+        constraint = task.node_assigned == Some(node.owner) @ ErrorCode::UnauthorizedNode
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        mut,
+        seeds = [Client::PREFIX.as_bytes(), task.owner.as_ref()],
+        bump = client.bump
+    )]
+    pub client: Box<Account<'info, Client>>,
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), node.owner.as_ref()],
+        bump
+    )]
+    pub node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        seeds = [ReputationConfig::PREFIX.as_bytes()],
+        bump = reputation_config.bump
+    )]
+    pub reputation_config: Box<Account<'info, ReputationConfig>>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), task.payment_mint.as_ref()],
+        bump = token_vault.bump,
+        constraint = !token_vault.paused @ ErrorCode::VaultPaused
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = task.payment_mint)]
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = node_token_account.mint == task.payment_mint,
+        constraint = node_token_account.owner == node.owner,
+    )]
+    pub node_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = BonusEpoch::SIZE,
+        seeds = [
+            BonusEpoch::PREFIX.as_bytes(),
+            BonusEpoch::current(config.params.bonus_epoch_length)?.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bonus_epoch: Box<Account<'info, BonusEpoch>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = BonusClaim::SIZE,
+        seeds = [
+            BonusClaim::PREFIX.as_bytes(),
+            node.owner.as_ref(),
+            BonusEpoch::current(config.params.bonus_epoch_length)?.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bonus_claim: Box<Account<'info, BonusClaim>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(never)]
+pub fn accept_result(ctx: Context<AcceptResultContext>) -> Result<()> {
+    let reward = release_reward(
+        &mut ctx.accounts.task,
+        &mut ctx.accounts.node,
+        &ctx.accounts.reputation_config,
+        &mut ctx.accounts.token_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.vault_token_account,
+        &mut ctx.accounts.node_token_account,
+        &ctx.accounts.token_program,
+        &ctx.accounts.config.params,
+    )?;
+    let spent = ctx.accounts.task.in_scrape(&ctx.accounts.config.params.scrape_mint, reward);
+    ctx.accounts.client.close_task(spent, true)?;
+    record_contribution(
+        &mut ctx.accounts.bonus_epoch,
+        ctx.bumps.bonus_epoch,
+        &mut ctx.accounts.bonus_claim,
+        ctx.bumps.bonus_claim,
+        ctx.accounts.node.owner,
+        BonusEpoch::current(ctx.accounts.config.params.bonus_epoch_length)?,
+        ctx.accounts.task.bonus_contribution(),
+    )?;
+
+    msg!(
+        "Task {} accepted by owner: {}. Rewards: {} to node: {}. Reputation: {}",
+        ctx.accounts.task.id,
+        ctx.accounts.signer.key(),
+        reward,
+        ctx.accounts.node.owner,
+        ctx.accounts.node.reputation
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeResultContext<'info> {
+    #[account(
+        mut,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = task.status == TaskStatus::Submitted @ ErrorCode::ResultNotSubmitted,
+        constraint = task.node_assigned == Some(node.owner) @ ErrorCode::UnauthorizedNode
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        mut,
+        seeds = [Client::PREFIX.as_bytes(), task.owner.as_ref()],
+        bump = client.bump
+    )]
+    pub client: Box<Account<'info, Client>>,
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), node.owner.as_ref()],
+        bump
+    )]
+    pub node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        seeds = [ReputationConfig::PREFIX.as_bytes()],
+        bump = reputation_config.bump
+    )]
+    pub reputation_config: Box<Account<'info, ReputationConfig>>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), task.payment_mint.as_ref()],
+        bump = token_vault.bump,
+        constraint = !token_vault.paused @ ErrorCode::VaultPaused
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = task.payment_mint)]
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = node_token_account.mint == task.payment_mint,
+        constraint = node_token_account.owner == node.owner,
+    )]
+    pub node_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = BonusEpoch::SIZE,
+        seeds = [
+            BonusEpoch::PREFIX.as_bytes(),
+            BonusEpoch::current(config.params.bonus_epoch_length)?.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bonus_epoch: Box<Account<'info, BonusEpoch>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = BonusClaim::SIZE,
+        seeds = [
+            BonusClaim::PREFIX.as_bytes(),
+            node.owner.as_ref(),
+            BonusEpoch::current(config.params.bonus_epoch_length)?.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bonus_claim: Box<Account<'info, BonusClaim>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: a result the owner did not review within the configured `review_window` is accepted automatically.
+#[inline(never)]
+pub fn finalize_result(ctx: Context<FinalizeResultContext>) -> Result<()> {
+    let review_closes_at = ctx
+        .accounts
+        .task
+        .submitted_at
+        .checked_add(ctx.accounts.config.params.review_window)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        Clock::get()?.unix_timestamp > review_closes_at,
+        ErrorCode::ReviewWindowOpen
+    );
+
+    let reward = release_reward(
+        &mut ctx.accounts.task,
+        &mut ctx.accounts.node,
+        &ctx.accounts.reputation_config,
+        &mut ctx.accounts.token_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.vault_token_account,
+        &mut ctx.accounts.node_token_account,
+        &ctx.accounts.token_program,
+        &ctx.accounts.config.params,
+    )?;
+    let spent = ctx.accounts.task.in_scrape(&ctx.accounts.config.params.scrape_mint, reward);
+    ctx.accounts.client.close_task(spent, true)?;
+    record_contribution(
+        &mut ctx.accounts.bonus_epoch,
+        ctx.bumps.bonus_epoch,
+        &mut ctx.accounts.bonus_claim,
+        ctx.bumps.bonus_claim,
+        ctx.accounts.node.owner,
+        BonusEpoch::current(ctx.accounts.config.params.bonus_epoch_length)?,
+        ctx.accounts.task.bonus_contribution(),
+    )?;
+
+    msg!(
+        "Task {} auto-accepted after review window. Rewards: {} to node: {}. Reputation: {}",
+        ctx.accounts.task.id,
+        reward,
+        ctx.accounts.node.owner,
+        ctx.accounts.node.reputation
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RejectResultContext<'info> {
+    #[account(
+        mut,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = task.owner == signer.key() @ ErrorCode::UnauthorizedNode,
+        constraint = task.status == TaskStatus::Submitted @ ErrorCode::ResultNotSubmitted
+    )]
+    pub task: Box<Account<'info, Task>>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+/// Rejects a submitted result. The provider has `review_window` to contest the rejection with
+/// `open_dispute`; until then, or until that dispute settles, the escrow stays locked.
+#[inline(never)]
+pub fn reject_result(ctx: Context<RejectResultContext>) -> Result<()> {
+    let task = &mut ctx.accounts.task;
+
+    let now = Clock::get()?.unix_timestamp;
+    let review_closes_at = task
+        .submitted_at
+        .checked_add(ctx.accounts.config.params.review_window)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(now <= review_closes_at, ErrorCode::ReviewWindowClosed);
+
+    task.status = TaskStatus::Rejected;
+    task.rejected_at = now;
+
+    msg!(
+        "Task {} result from node {:?} rejected by owner: {}",
+        task.id,
+        task.node_assigned,
+        task.owner
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeRejectionContext<'info> {
+    #[account(
+        mut,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = task.status == TaskStatus::Rejected @ ErrorCode::ResultNotRejected,
+        constraint = task.node_assigned == Some(node.owner) @ ErrorCode::UnauthorizedNode
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        mut,
+        seeds = [ProviderNode::PREFIX.as_bytes(), node.owner.as_ref()],
+        bump
+    )]
+    pub node: Box<Account<'info, ProviderNode>>,
+    #[account(
+        seeds = [ReputationConfig::PREFIX.as_bytes()],
+        bump = reputation_config.bump
+    )]
+    pub reputation_config: Box<Account<'info, ReputationConfig>>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), task.payment_mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+}
+
+/// Permissionless: a rejection the provider did not dispute within `review_window` stands. The
+/// node takes the reputation hit and the task goes back in the queue with its escrow still locked.
+#[inline(never)]
+pub fn finalize_rejection(ctx: Context<FinalizeRejectionContext>) -> Result<()> {
+    let task = &mut ctx.accounts.task;
+    let node = &mut ctx.accounts.node;
+
+    let now = Clock::get()?.unix_timestamp;
+    let dispute_closes_at = task
+        .rejected_at
+        .checked_add(ctx.accounts.config.params.review_window)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(now > dispute_closes_at, ErrorCode::ReviewWindowOpen);
+
+    node.record_reputation_event(
+        &ctx.accounts.reputation_config.weights,
+        ReputationEvent::TaskRejected,
+        now,
+    );
+
+    // The rejected dataset no longer counts against what the client paid for
+    let token_vault = &mut ctx.accounts.token_vault;
+    token_vault.bandwidth_used_mb = token_vault.bandwidth_used_mb.saturating_sub(task.dataset_size_mb());
+    token_vault.bytes_delivered = token_vault.bytes_delivered.saturating_sub(task.dataset_size);
+
+    // Put the task back in the queue; the escrow stays locked for the next node
+    node.active_tasks = node.active_tasks.saturating_sub(1);
+    task.status = TaskStatus::Pending;
+    task.node_assigned = None;
+    task.assigned_at = 0;
+    task.assigned_by = None;
+    task.selected_node = None;
+    task.selection = None;
+    task.sealed_connection = Vec::new();
+    task.submitted_at = 0;
+    task.rejected_at = 0;
+    task.dataset_uri = None;
+    task.dataset_size = 0;
+    task.record_count = 0;
+    task.content_hash = [0; 32];
+
+    msg!(
+        "Task {} rejection of node {} finalized, back in the queue. Reputation: {}",
+        task.id,
+        node.owner,
+        node.reputation
+    );
+    Ok(())
+}
+
+/// Pays the task's escrow, less the task fee, to the node and marks the task as completed.
+#[allow(clippy::too_many_arguments)]
+fn release_reward<'info>(
+    task: &mut Account<'info, Task>,
+    node: &mut Account<'info, ProviderNode>,
+    reputation_config: &Account<'info, ReputationConfig>,
+    token_vault: &mut Account<'info, TokenVault>,
+    payment_mint: &InterfaceAccount<'info, Mint>,
+    vault_token_account: &InterfaceAccount<'info, TokenAccount>,
+    node_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    params: &ConfigParams,
+) -> Result<u64> {
+    let reward = task.escrow;
+
+    if reward > 0 {
+        token_vault.release_escrow(reward)?;
+
+        // The fee stays in the vault, moving from the escrow pool to the treasury
+        let fee = params.task_fee(reward)?;
+        let payout = reward - fee;
+        token_vault.credit_treasury(fee)?;
+
+        let mint = token_vault.mint;
+        let bump = [token_vault.bump];
+        let seeds: &[&[u8]] = &[TokenVault::PREFIX.as_bytes(), mint.as_ref(), &bump];
+        transfer_tokens(
+            token_program,
+            vault_token_account,
+            node_token_account,
+            payment_mint,
+            token_vault.to_account_info(),
+            &[seeds],
+            payout,
+        )?;
+
+        token_vault.total_rewards_distributed = token_vault
+            .total_rewards_distributed
+            .checked_add(payout)
+            .ok_or(ErrorCode::MathOverflow)?;
+        node.rewards = node
+            .rewards
+            .checked_add(task.in_scrape(&params.scrape_mint, payout))
+            .ok_or(ErrorCode::MathOverflow)?;
+        task.escrow = 0;
+    }
+
+    task.status = TaskStatus::Completed;
+    node.active_tasks = node.active_tasks.saturating_sub(1);
+    node.record_reputation_event(
+        &reputation_config.weights,
+        ReputationEvent::TaskAccepted,
+        Clock::get()?.unix_timestamp,
+    );
+
+    Ok(reward)
+}
@@ -0,0 +1,287 @@
+use crate::error::ErrorCode;
+use crate::events::ProviderSelected;
+use crate::state::config::Config;
+use crate::state::endpoint_node::EndpointNode;
+use crate::state::node_registry::{NodeRegistry, RegistryPage};
+use crate::state::provider_node::ProviderNode;
+use crate::state::randomness::{draw_key, selection_seed, slot_hash_from, RandomnessFeed, SEED_DELAY_SLOTS};
+use crate::state::reputation::ReputationConfig;
+use crate::state::task::{SelectionState, Task, TaskStatus};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::slot_hashes::MAX_ENTRIES;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+
+#[derive(Accounts)]
+pub struct SelectProviderContext<'info> {
+    #[account(
+        mut,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        has_one = endpoint_node @ ErrorCode::UnauthorizedNode,
+        constraint = task.status == TaskStatus::Pending @ ErrorCode::TaskNotAssigned,
+        constraint = task.selected_node.is_none() @ ErrorCode::ProviderAlreadySelected
+    )]
+    pub task: Box<Account<'info, Task>>,
+    pub endpoint_node: Box<Account<'info, EndpointNode>>,
+    #[account(
+        seeds = [NodeRegistry::PREFIX.as_bytes()],
+        bump = node_registry.bump
+    )]
+    pub node_registry: Box<Account<'info, NodeRegistry>>,
+    #[account(
+        seeds = [
+            RegistryPage::PREFIX.as_bytes(),
+            task.selection.as_ref().map_or(0, |s| s.next_page).to_le_bytes().as_ref()
+        ],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Box<Account<'info, RegistryPage>>,
+    /// CHECK: address is constrained to the SlotHashes sysvar, whose data is parsed manually
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    pub randomness_feed: Option<Box<Account<'info, RandomnessFeed>>>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [ReputationConfig::PREFIX.as_bytes()],
+        bump = reputation_config.bump
+    )]
+    pub reputation_config: Box<Account<'info, ReputationConfig>>,
+    #[account(
+        seeds = [ProviderNode::PREFIX.as_bytes(), candidate_node.owner.as_ref()],
+        bump
+    )]
+    pub candidate_node: Option<Box<Account<'info, ProviderNode>>>, // Last page only: the candidate drawn on an earlier page, if any
+    // remaining_accounts: every ProviderNode listed on `registry_page`, in slot order
+}
+
+/// Draws the provider for a pending task, weighted by reputation, stake and bandwidth headroom.
+/// Registry pages are processed in index order, one per call, as an exponential race: the seed is
+/// fixed before the first page, every node gets a `draw_key`, and the lowest key once the last page
+/// has been processed wins. The winner does not depend on page order, so when compaction moves
+/// nodes mid-draw the pages are simply processed again with the same seed.
+///
+/// The first call only commits to randomness nobody knows yet, and the next call reveals it.
+/// Without a randomness feed the seed is the hash of the first slot at or after one
+/// `SEED_DELAY_SLOTS` ahead that produced a block, so neither the caller nor the nodes can grind it
+/// by picking when to send the transaction. The accepted residual risk is the leader of the
+/// committed slot: it can withhold its block and defer to the next leader's hash, one extra roll
+/// per validator that colludes. Deployments that cannot accept this configure a VRF oracle as
+/// `randomness_feed`, whose authority is then trusted to publish unbiased values; the draw commits
+/// to the feed's next round and reveals with the value published for it.
+#[inline(never)]
+pub fn select_provider<'info>(ctx: Context<'_, '_, 'info, 'info, SelectProviderContext<'info>>) -> Result<()> {
+    let task_key = ctx.accounts.task.key();
+    let task = &mut ctx.accounts.task;
+    let params = &ctx.accounts.config.params;
+    let node_registry = &ctx.accounts.node_registry;
+    let signer = ctx.accounts.signer.key();
+
+    // Only the parties that could assign the task may drive the draw
+    require!(
+        signer == task.owner || signer == ctx.accounts.endpoint_node.owner,
+        ErrorCode::UnauthorizedAssigner
+    );
+
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp <= task.deadline, ErrorCode::TaskExpired);
+
+    let mut state = match task.selection.take() {
+        Some(state) => state,
+        // Commit to the randomness source configured by the admin
+        None => {
+            let mut state = SelectionState {
+                registry_epoch: node_registry.epoch,
+                ..Default::default()
+            };
+            match params.randomness_feed {
+                Some(feed_key) => {
+                    let feed = configured_feed(&ctx.accounts.randomness_feed, feed_key)?;
+                    state.commit_round = feed.round.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+                    msg!("Task {} draw committed to randomness round {}", task.id, state.commit_round);
+                }
+                None => {
+                    state.commit_slot = clock.slot + SEED_DELAY_SLOTS;
+                    msg!("Task {} draw committed to slot {}", task.id, state.commit_slot);
+                }
+            }
+            task.selection = Some(state);
+            return Ok(());
+        }
+    };
+
+    // Reveal the committed feed round, or commit again once the feed has moved past it
+    if state.commit_round != 0 {
+        let Some(feed_key) = params.randomness_feed else {
+            msg!("Task {}: randomness feed removed before the reveal, draw reset", task.id);
+            return Ok(());
+        };
+        let feed = configured_feed(&ctx.accounts.randomness_feed, feed_key)?;
+        require!(feed.round >= state.commit_round, ErrorCode::RandomnessNotReady);
+        if feed.round > state.commit_round {
+            state.commit_round = feed.round.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+            msg!("Task {} draw committed again to randomness round {}", task.id, state.commit_round);
+            task.selection = Some(state);
+            return Ok(());
+        }
+        state.seed = selection_seed(&feed.value, &task_key, feed.slot);
+        state.commit_round = 0;
+    }
+
+    // Reveal the committed slot hash, or commit again once it may have aged out of the sysvar
+    if state.commit_slot != 0 {
+        require!(clock.slot > state.commit_slot, ErrorCode::RandomnessNotReady);
+        let data = ctx.accounts.slot_hashes.try_borrow_data()?;
+        match slot_hash_from(&data, state.commit_slot) {
+            Some((slot, hash)) => {
+                state.seed = selection_seed(&hash, &task_key, slot);
+                state.commit_slot = 0;
+            }
+            None if clock.slot - state.commit_slot < MAX_ENTRIES as u64 => {
+                return err!(ErrorCode::RandomnessNotReady);
+            }
+            None => {
+                state.commit_slot = clock.slot + SEED_DELAY_SLOTS;
+                msg!("Task {} draw committed again to slot {}", task.id, state.commit_slot);
+                task.selection = Some(state);
+                return Ok(());
+            }
+        }
+    }
+
+    // Compaction may have moved nodes onto pages this pass has already processed. The seed stays,
+    // so the pass over the pages is repeated without re-rolling the draw
+    if state.registry_epoch != node_registry.epoch {
+        let mid_pass = state.next_page != 0;
+        state = state.restarted(node_registry.epoch);
+        if mid_pass {
+            msg!("Task {}: registry compacted during the draw, restarting from the first page", task.id);
+            task.selection = Some(state);
+            return Ok(());
+        }
+    }
+
+    let carried = state.candidate;
+
+    // Every node on the page must be supplied so the caller cannot narrow the candidate set
+    let listed: Vec<Pubkey> = ctx.accounts.registry_page.nodes().copied().collect();
+    require!(
+        ctx.remaining_accounts.len() == listed.len(),
+        ErrorCode::IncompleteCandidateSet
+    );
+    for (account, owner) in ctx.remaining_accounts.iter().zip(listed.iter()) {
+        let node = Account::<ProviderNode>::try_from(account)?;
+        require!(node.owner == *owner, ErrorCode::IncompleteCandidateSet);
+
+        let weight = node.selection_weight(
+            params.min_stake,
+            &task.requirements,
+            clock.unix_timestamp,
+            ctx.accounts.reputation_config.weights.half_life,
+        );
+        if weight == 0 {
+            continue;
+        }
+        let key = draw_key(&state.seed, owner, weight);
+        if state.candidate.is_none() || key < state.best_key {
+            state.best_key = key;
+            state.candidate = Some(*owner);
+        }
+    }
+    state.next_page += 1;
+
+    if state.next_page < node_registry.page_count {
+        msg!("Task {} draw processed page {} of {}", task.id, state.next_page, node_registry.page_count);
+        task.selection = Some(state);
+        return Ok(());
+    }
+
+    // Last page processed. A candidate carried over from an earlier page may have deregistered or
+    // become unselectable since it was weighed; it then drops out of a repeated pass with the same
+    // seed. Registrations need no epoch bump: a node seen twice keeps its key, so the winner holds
+    if let Some(node) = state.candidate.filter(|node| carried == Some(*node)) {
+        let candidate_node = ctx
+            .accounts
+            .candidate_node
+            .as_ref()
+            .filter(|candidate_node| candidate_node.owner == node)
+            .ok_or(ErrorCode::IncompleteCandidateSet)?;
+        if !candidate_node.is_selectable(params.min_stake, &task.requirements) {
+            msg!("Task {}: candidate {} is no longer selectable, restarting from the first page", task.id, node);
+            task.selection = Some(state.restarted(node_registry.epoch));
+            return Ok(());
+        }
+    }
+
+    match state.candidate {
+        Some(node) => {
+            task.selected_node = Some(node);
+            emit!(ProviderSelected {
+                task: task_key,
+                node,
+                seed: state.seed,
+                slot: clock.slot,
+            });
+            msg!("Task {} selected provider node: {}", task.id, node);
+        }
+        None => msg!("Task {}: no eligible provider node, draw reset", task.id),
+    }
+    Ok(())
+}
+
+/// The supplied feed, if it is the one `feed_key` configures.
+fn configured_feed<'a>(
+    feed: &'a Option<Box<Account<'_, RandomnessFeed>>>,
+    feed_key: Pubkey,
+) -> Result<&'a RandomnessFeed> {
+    let feed = feed.as_ref().ok_or(ErrorCode::InvalidRandomness)?;
+    require!(feed.key() == feed_key, ErrorCode::InvalidRandomness);
+    Ok(feed)
+}
+
+#[derive(Accounts)]
+pub struct ResetSelectionContext<'info> {
+    #[account(
+        mut,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = task.status == TaskStatus::Pending @ ErrorCode::TaskNotAssigned,
+        constraint = task.selected_node == Some(selected_node.owner) @ ErrorCode::ProviderNotSelected
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        seeds = [ProviderNode::PREFIX.as_bytes(), selected_node.owner.as_ref()],
+        bump
+    )]
+    pub selected_node: Box<Account<'info, ProviderNode>>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+/// Permissionless: clears the selection of a pending task whose selected node has since been
+/// deregistered, paused, dropped below the minimum stake or stopped meeting the task's requirements, so that
+/// `select_provider` can draw again.
+#[inline(never)]
+pub fn reset_selection(ctx: Context<ResetSelectionContext>) -> Result<()> {
+    let task = &mut ctx.accounts.task;
+    let selected_node = &ctx.accounts.selected_node;
+
+    require!(
+        !selected_node.is_selectable(ctx.accounts.config.params.min_stake, &task.requirements),
+        ErrorCode::ProviderStillSelectable
+    );
+
+    task.selected_node = None;
+    task.selection = None;
+
+    msg!("Task {}: selected node {} is no longer selectable, selection reset", task.id, selected_node.owner);
+    Ok(())
+}
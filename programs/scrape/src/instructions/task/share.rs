@@ -0,0 +1,44 @@
+use crate::error::ErrorCode;
+use crate::state::provider_node::ProviderNode;
+use crate::state::task::{Task, TaskStatus};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ShareConnectionContext<'info> {
+    #[account(
+        mut,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = task.status == TaskStatus::Assigned @ ErrorCode::TaskNotAssigned,
+        constraint = task.node_assigned == Some(node.owner) @ ErrorCode::UnauthorizedNode
+    )]
+    pub task: Box<Account<'info, Task>>,
+    #[account(
+        seeds = [ProviderNode::PREFIX.as_bytes(), node.owner.as_ref()],
+        bump
+    )]
+    pub node: Box<Account<'info, ProviderNode>>,
+    pub signer: Signer<'info>,
+}
+
+/// Hands the task's endpoint node the assigned provider's connection info, sealed off-chain to the
+/// endpoint's `encryption_key`. It is cleared once the task leaves the Assigned state.
+#[inline(never)]
+pub fn share_connection(ctx: Context<ShareConnectionContext>, sealed_connection: Vec<u8>) -> Result<()> {
+    let task = &mut ctx.accounts.task;
+    let node = &ctx.accounts.node;
+
+    require!(
+        node.is_operator(&ctx.accounts.signer.key(), Clock::get()?.unix_timestamp),
+        ErrorCode::UnauthorizedNode
+    );
+    require!(
+        !sealed_connection.is_empty() && sealed_connection.len() <= Task::MAX_SEALED_CONNECTION_LEN,
+        ErrorCode::InvalidSealedConnection
+    );
+
+    task.sealed_connection = sealed_connection;
+
+    msg!("Task {}: node {} shared sealed connection info with its endpoint", task.id, node.owner);
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::error::ErrorCode;
+use crate::state::config::Config;
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateTokenVaultContext<'info> {
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), token_vault.mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub admin: Signer<'info>,
+}
+
+/// Adds the vault's mint to, or removes it from, the mints accepted for new task rewards. Tasks
+/// already escrowed in a withdrawn mint still settle normally.
+pub fn update(ctx: Context<UpdateTokenVaultContext>, approved: bool, rate_per_mb: u64) -> Result<()> {
+    require!(rate_per_mb > 0, ErrorCode::InvalidConfig);
+
+    let token_vault = &mut ctx.accounts.token_vault;
+    token_vault.approved = approved;
+    token_vault.rate_per_mb = rate_per_mb;
+
+    msg!(
+        "Token vault for mint {} updated. Approved: {}, rate per MB: {}",
+        token_vault.mint,
+        approved,
+        rate_per_mb
+    );
+    Ok(())
+}
@@ -1,41 +1,55 @@
+use crate::error::ErrorCode;
 use crate::state::token::TokenVault;
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::state::config::Config;
 
 #[derive(Accounts)]
 pub struct InitializeTokenVaultContext<'info> {
     #[account(mut)]
-    pub signer: Signer<'info>,
+    pub admin: Signer<'info>,
     #[account(
         init,
-        payer = signer,
+        payer = admin,
         space = TokenVault::SIZE,
-        seeds = [TokenVault::PREFIX.as_bytes()],
+        seeds = [TokenVault::PREFIX.as_bytes(), mint.key().as_ref()],
         bump
     )]
     pub token_vault: Box<Account<'info, TokenVault>>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
-        constraint = vault_token_account.mint == crate::SCRAPE_MINT.parse::<Pubkey>().unwrap(),
+        constraint = vault_token_account.mint == mint.key(),
         constraint = vault_token_account.owner == token_vault.key(),
     )]
-    pub vault_token_account: Box<Account<'info, TokenAccount>>,
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub config: Box<Account<'info, Config>>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+/// Creates the vault for `mint` and approves the mint for task rewards. `owner` becomes the vault's
+/// treasury authority, e.g. the admin or the TreasuryMultisig PDA.
+pub fn init(ctx: Context<InitializeTokenVaultContext>, owner: Pubkey, rate_per_mb: u64) -> Result<()> {
+    require!(rate_per_mb > 0, ErrorCode::InvalidConfig);
 
-pub fn init(ctx: Context<InitializeTokenVaultContext>) -> Result<()> {
-    let signer = &ctx.accounts.signer;
     let token_vault = &mut ctx.accounts.token_vault;
     let vault_token_account = &ctx.accounts.vault_token_account;
 
     token_vault.bump = ctx.bumps.token_vault;
-    token_vault.owner = signer.key(); // Set the owner to the signer (can be changed to Pubkey::default() if no admin is needed)
+    token_vault.owner = owner;
     token_vault.token_account = vault_token_account.key();
     token_vault.total_rewards_distributed = 0;
-    token_vault.bandwidth_paid = 0;
-    token_vault.bandwidth_used = 0;
+    token_vault.bandwidth_paid_mb = 0;
+    token_vault.bandwidth_used_mb = 0;
+    token_vault.mint = ctx.accounts.mint.key();
+    token_vault.approved = true;
+    token_vault.rate_per_mb = rate_per_mb;
 
-    msg!("Token vault initialized: {}", token_vault.key());
+    msg!("Token vault initialized: {} for mint: {}", token_vault.key(), token_vault.mint);
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,155 @@
+use crate::error::ErrorCode;
+use crate::instructions::token_vault::transfer_tokens;
+use crate::state::config::Config;
+use crate::state::task::Task;
+use crate::state::token::{LegacyTokenVault, TokenVault};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct MigrateTokenVaultContext<'info> {
+    /// CHECK: the legacy layout cannot be deserialized as the current one; it is parsed by
+    /// `LegacyTokenVault::parse`, which checks the discriminator and size
+    #[account(
+        mut,
+        owner = crate::ID @ ErrorCode::InvalidVaultMigration,
+        seeds = [TokenVault::PREFIX.as_bytes()],
+        bump
+    )]
+    pub legacy_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub legacy_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), config.params.scrape_mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = config.params.scrape_mint)]
+    pub scrape_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Drains the vault kept under the single `[TOKEN_VAULT]` seed into the per-mint $SCRAPE vault.
+/// The token balance moves over, the escrow, bonus and treasury pools and the running counters are
+/// added to the new vault's, and anything the legacy vault held beyond its pools goes to the
+/// treasury. The legacy vault and its token account are then closed, with their rent going to the
+/// admin. Tasks opened before the migration still need `migrate_task`.
+#[inline(never)]
+pub fn migrate(ctx: Context<MigrateTokenVaultContext>) -> Result<()> {
+    let info = ctx.accounts.legacy_vault.to_account_info();
+    let (legacy, pools) =
+        LegacyTokenVault::parse(&info.try_borrow_data()?).ok_or(ErrorCode::InvalidVaultMigration)?;
+    require_keys_eq!(
+        ctx.accounts.legacy_token_account.key(),
+        legacy.token_account,
+        ErrorCode::InvalidVaultMigration
+    );
+
+    let bump = [legacy.bump];
+    let seeds: &[&[u8]] = &[TokenVault::PREFIX.as_bytes(), &bump];
+    let signer_seeds = &[seeds];
+    let balance = ctx.accounts.legacy_token_account.amount;
+    let received = if balance > 0 {
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.legacy_token_account,
+            &mut ctx.accounts.vault_token_account,
+            &ctx.accounts.scrape_mint,
+            info.clone(),
+            signer_seeds,
+            balance,
+        )?
+    } else {
+        0
+    };
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.legacy_token_account.to_account_info(),
+        destination: ctx.accounts.admin.to_account_info(),
+        authority: info.clone(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token_interface::close_account(cpi_ctx)?;
+
+    let vault_key = ctx.accounts.token_vault.key();
+    let token_vault: &mut TokenVault = &mut ctx.accounts.token_vault;
+    let owed = pools
+        .total_escrowed
+        .checked_add(pools.bonus_pool)
+        .and_then(|amount| amount.checked_add(pools.treasury))
+        .ok_or(ErrorCode::MathOverflow)?;
+    token_vault.lock_escrow(pools.total_escrowed)?;
+    token_vault.fund_bonus(pools.bonus_pool)?;
+    token_vault.credit_treasury(pools.treasury.saturating_add(received.saturating_sub(owed)))?;
+
+    let counters = [
+        (&mut token_vault.total_rewards_distributed, legacy.total_rewards_distributed),
+        (&mut token_vault.bandwidth_paid_mb, legacy.bandwidth_paid_mb),
+        (&mut token_vault.bandwidth_used_mb, legacy.bandwidth_used_mb),
+        (&mut token_vault.total_slashed, pools.total_slashed),
+        (&mut token_vault.total_fees_collected, pools.total_fees_collected),
+        (&mut token_vault.total_escrow_deposited, pools.total_escrow_deposited),
+        (&mut token_vault.bytes_delivered, pools.bytes_delivered),
+    ];
+    for (counter, legacy_value) in counters {
+        *counter = counter.checked_add(legacy_value).ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    // Close the legacy vault account
+    let admin = ctx.accounts.admin.to_account_info();
+    **admin.try_borrow_mut_lamports()? += info.lamports();
+    **info.try_borrow_mut_lamports()? = 0;
+    info.assign(&system_program::ID);
+    info.realloc(0, false)?;
+
+    msg!(
+        "Legacy vault drained into {}: {} received, {} escrowed, {} bonus pool. Treasury: {}",
+        vault_key,
+        received,
+        pools.total_escrowed,
+        pools.bonus_pool,
+        token_vault.treasury
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateTaskContext<'info> {
+    #[account(
+        mut,
+        seeds = [Task::PREFIX.as_bytes(), task.owner.as_ref(), task.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = task.payment_mint == Pubkey::default() @ ErrorCode::InvalidVaultMigration
+    )]
+    pub task: Box<Account<'info, Task>>,
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+/// Permissionless: points a task created before per-mint vaults at the $SCRAPE vault. Such tasks
+/// were always paid in $SCRAPE, and their escrow moved there with `migrate_token_vault`.
+#[inline(never)]
+pub fn migrate_task(ctx: Context<MigrateTaskContext>) -> Result<()> {
+    let task = &mut ctx.accounts.task;
+    task.payment_mint = ctx.accounts.config.params.scrape_mint;
+
+    msg!("Task {} of owner {} now pays in {}", task.id, task.owner, task.payment_mint);
+    Ok(())
+}
@@ -1,3 +1,11 @@
+pub mod approve;
 pub mod init;
+pub mod migrate;
+pub mod reconcile;
+pub mod transfer;
 
-pub use init::*;
\ No newline at end of file
+pub use approve::*;
+pub use init::*;
+pub use migrate::*;
+pub use reconcile::*;
+pub use transfer::*;
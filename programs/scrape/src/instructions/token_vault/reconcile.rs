@@ -0,0 +1,91 @@
+use crate::error::ErrorCode;
+use crate::events::VaultReconciled;
+use crate::state::config::Config;
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+#[derive(Accounts)]
+pub struct ReconcileVaultContext<'info> {
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), token_vault.mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub signer: Signer<'info>,
+}
+
+/// Permissionless: checks the vault's recorded pools against its token balance and trips the
+/// circuit breaker if they are not fully backed.
+#[inline(never)]
+pub fn reconcile(ctx: Context<ReconcileVaultContext>) -> Result<()> {
+    let token_vault = &mut ctx.accounts.token_vault;
+    let balance = ctx.accounts.vault_token_account.amount;
+    let liabilities = token_vault.liabilities()?;
+    let solvent = token_vault.is_solvent(balance)?;
+    let slot = Clock::get()?.slot;
+
+    if !solvent {
+        token_vault.paused = true;
+    }
+    token_vault.last_reconciled_slot = slot;
+
+    emit!(VaultReconciled {
+        balance,
+        escrow: token_vault.total_escrowed,
+        bonus_pool: token_vault.bonus_pool,
+        treasury: token_vault.treasury,
+        shortfall: liabilities.saturating_sub(balance),
+        surplus: balance.saturating_sub(liabilities),
+        bandwidth_paid_mb: token_vault.bandwidth_paid_mb,
+        bandwidth_used_mb: token_vault.bandwidth_used_mb,
+        paused: token_vault.paused,
+        slot,
+    });
+
+    msg!(
+        "Vault reconciled. Balance: {}, liabilities: {}. Paused: {}",
+        balance,
+        liabilities,
+        token_vault.paused
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResumeVaultContext<'info> {
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), token_vault.mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub admin: Signer<'info>,
+}
+
+/// Clears the circuit breaker once the vault reconciles again, e.g. after the shortfall was topped up.
+#[inline(never)]
+pub fn resume(ctx: Context<ResumeVaultContext>) -> Result<()> {
+    let token_vault = &mut ctx.accounts.token_vault;
+    require!(
+        token_vault.is_solvent(ctx.accounts.vault_token_account.amount)?,
+        ErrorCode::VaultInsolvent
+    );
+
+    token_vault.paused = false;
+    token_vault.last_reconciled_slot = Clock::get()?.slot;
+
+    msg!("Vault resumed by admin: {}", ctx.accounts.admin.key());
+    Ok(())
+}
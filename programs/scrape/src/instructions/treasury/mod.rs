@@ -0,0 +1,7 @@
+pub mod multisig;
+pub mod proposal;
+pub mod withdraw;
+
+pub use multisig::*;
+pub use proposal::*;
+pub use withdraw::*;
@@ -0,0 +1,80 @@
+use crate::error::ErrorCode;
+use crate::state::config::Config;
+use crate::state::token::TokenVault;
+use crate::state::treasury::TreasuryMultisig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeTreasuryMultisigContext<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = TreasuryMultisig::SIZE,
+        seeds = [TreasuryMultisig::PREFIX.as_bytes()],
+        bump
+    )]
+    pub treasury_multisig: Box<Account<'info, TreasuryMultisig>>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [Config::PREFIX.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the M-of-N multisig that vault owners can hand their vaults to. Admin-only, so that the
+/// singleton cannot be claimed by whoever initializes it first.
+pub fn init_multisig(
+    ctx: Context<InitializeTreasuryMultisigContext>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(
+        TreasuryMultisig::is_valid_signer_set(&signers, threshold),
+        ErrorCode::InvalidMultisig
+    );
+
+    let treasury_multisig = &mut ctx.accounts.treasury_multisig;
+    treasury_multisig.bump = ctx.bumps.treasury_multisig;
+    treasury_multisig.signers = signers;
+    treasury_multisig.threshold = threshold;
+    treasury_multisig.signer_set_version = 0;
+    treasury_multisig.proposal_count = 0;
+
+    msg!(
+        "Treasury multisig initialized: {} of {} signers",
+        threshold,
+        treasury_multisig.signers.len()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct HandOverVaultContext<'info> {
+    #[account(
+        seeds = [TreasuryMultisig::PREFIX.as_bytes()],
+        bump = treasury_multisig.bump
+    )]
+    pub treasury_multisig: Box<Account<'info, TreasuryMultisig>>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), token_vault.mint.as_ref()],
+        bump = token_vault.bump,
+        constraint = token_vault.owner == signer.key() @ ErrorCode::UnauthorizedTreasury
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    pub signer: Signer<'info>,
+}
+
+/// Replaces a vault's single-key owner with the multisig. Signed by the current owner; afterwards
+/// the owner can only change through an executed `SetOwner` proposal.
+pub fn hand_over_vault(ctx: Context<HandOverVaultContext>) -> Result<()> {
+    let token_vault = &mut ctx.accounts.token_vault;
+    token_vault.owner = ctx.accounts.treasury_multisig.key();
+
+    msg!("Vault of mint {} handed over to multisig: {}", token_vault.mint, token_vault.owner);
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use crate::error::ErrorCode;
+use crate::instructions::treasury::pay_from_treasury;
+use crate::state::token::TokenVault;
+use crate::state::treasury::{TreasuryAction, TreasuryMultisig, TreasuryProposal};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ProposeTreasuryActionContext<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryMultisig::PREFIX.as_bytes()],
+        bump = treasury_multisig.bump
+    )]
+    pub treasury_multisig: Box<Account<'info, TreasuryMultisig>>,
+    #[account(
+        init,
+        payer = signer,
+        space = TreasuryProposal::SIZE,
+        seeds = [
+            TreasuryProposal::PREFIX.as_bytes(),
+            treasury_multisig.proposal_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub treasury_proposal: Box<Account<'info, TreasuryProposal>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a proposal for `action`; the proposer's approval is recorded with it.
+#[inline(never)]
+pub fn propose(ctx: Context<ProposeTreasuryActionContext>, action: TreasuryAction) -> Result<()> {
+    let treasury_multisig = &mut ctx.accounts.treasury_multisig;
+    let signer = ctx.accounts.signer.key();
+    let index = treasury_multisig
+        .signer_index(&signer)
+        .ok_or(ErrorCode::UnauthorizedTreasury)?;
+    require!(action.is_valid(), ErrorCode::InvalidTreasuryAction);
+
+    let treasury_proposal = &mut ctx.accounts.treasury_proposal;
+    treasury_proposal.bump = ctx.bumps.treasury_proposal;
+    treasury_proposal.id = treasury_multisig.proposal_count;
+    treasury_proposal.proposer = signer;
+    treasury_proposal.action = action;
+    treasury_proposal.approvals = 1 << index;
+    treasury_proposal.signer_set_version = treasury_multisig.signer_set_version;
+    treasury_proposal.executed = false;
+    treasury_proposal.created_at = Clock::get()?.unix_timestamp;
+
+    treasury_multisig.proposal_count = treasury_multisig
+        .proposal_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    msg!(
+        "Treasury proposal {} opened by {}: {:?}",
+        treasury_proposal.id,
+        signer,
+        treasury_proposal.action
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApproveTreasuryProposalContext<'info> {
+    #[account(
+        seeds = [TreasuryMultisig::PREFIX.as_bytes()],
+        bump = treasury_multisig.bump
+    )]
+    pub treasury_multisig: Box<Account<'info, TreasuryMultisig>>,
+    #[account(
+        mut,
+        seeds = [TreasuryProposal::PREFIX.as_bytes(), treasury_proposal.id.to_le_bytes().as_ref()],
+        bump = treasury_proposal.bump,
+        constraint = !treasury_proposal.executed @ ErrorCode::ProposalExecuted,
+        constraint = treasury_proposal.signer_set_version == treasury_multisig.signer_set_version
+            @ ErrorCode::StaleProposal
+    )]
+    pub treasury_proposal: Box<Account<'info, TreasuryProposal>>,
+    pub signer: Signer<'info>,
+}
+
+#[inline(never)]
+pub fn approve(ctx: Context<ApproveTreasuryProposalContext>) -> Result<()> {
+    let treasury_proposal = &mut ctx.accounts.treasury_proposal;
+    let index = ctx
+        .accounts
+        .treasury_multisig
+        .signer_index(&ctx.accounts.signer.key())
+        .ok_or(ErrorCode::UnauthorizedTreasury)?;
+    require!(treasury_proposal.approvals & (1 << index) == 0, ErrorCode::AlreadyApproved);
+
+    treasury_proposal.approvals |= 1 << index;
+
+    msg!(
+        "Treasury proposal {} approved by {}. Approvals: {}/{}",
+        treasury_proposal.id,
+        ctx.accounts.signer.key(),
+        treasury_proposal.approval_count(),
+        ctx.accounts.treasury_multisig.threshold
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTreasuryProposalContext<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryMultisig::PREFIX.as_bytes()],
+        bump = treasury_multisig.bump
+    )]
+    pub treasury_multisig: Box<Account<'info, TreasuryMultisig>>,
+    #[account(
+        mut,
+        seeds = [TreasuryProposal::PREFIX.as_bytes(), treasury_proposal.id.to_le_bytes().as_ref()],
+        bump = treasury_proposal.bump,
+        constraint = !treasury_proposal.executed @ ErrorCode::ProposalExecuted,
+        constraint = treasury_proposal.signer_set_version == treasury_multisig.signer_set_version
+            @ ErrorCode::StaleProposal
+    )]
+    pub treasury_proposal: Box<Account<'info, TreasuryProposal>>,
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), token_vault.mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = token_vault.mint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub destination_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>, // Required for withdrawals
+    pub signer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless once the proposal has `threshold` approvals from the current signer set.
+#[inline(never)]
+pub fn execute(ctx: Context<ExecuteTreasuryProposalContext>) -> Result<()> {
+    let treasury_multisig = &mut ctx.accounts.treasury_multisig;
+    let treasury_proposal = &mut ctx.accounts.treasury_proposal;
+    let token_vault = &mut ctx.accounts.token_vault;
+
+    require!(
+        treasury_proposal.approval_count() >= treasury_multisig.threshold as u32,
+        ErrorCode::ThresholdNotMet
+    );
+    treasury_proposal.executed = true;
+
+    match treasury_proposal.action.clone() {
+        TreasuryAction::Withdraw { amount, destination } => {
+            require!(token_vault.owner == treasury_multisig.key(), ErrorCode::UnauthorizedTreasury);
+            require!(!token_vault.paused, ErrorCode::VaultPaused);
+            let destination_token_account = ctx
+                .accounts
+                .destination_token_account
+                .as_mut()
+                .filter(|account| account.key() == destination && account.mint == token_vault.mint)
+                .ok_or(ErrorCode::InvalidWithdrawalDestination)?;
+            pay_from_treasury(
+                token_vault,
+                &ctx.accounts.mint,
+                &ctx.accounts.vault_token_account,
+                destination_token_account,
+                &ctx.accounts.token_program,
+                amount,
+            )?;
+            msg!("Treasury proposal {} executed: withdrew {} to {}", treasury_proposal.id, amount, destination);
+        }
+        TreasuryAction::SetSigners { signers, threshold } => {
+            treasury_multisig.signers = signers;
+            treasury_multisig.threshold = threshold;
+            treasury_multisig.signer_set_version = treasury_multisig
+                .signer_set_version
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+            msg!(
+                "Treasury proposal {} executed: signer set replaced, {} of {}",
+                treasury_proposal.id,
+                threshold,
+                treasury_multisig.signers.len()
+            );
+        }
+        TreasuryAction::SetOwner { mint, owner } => {
+            require!(token_vault.owner == treasury_multisig.key(), ErrorCode::UnauthorizedTreasury);
+            require_keys_eq!(token_vault.mint, mint, ErrorCode::InvalidTreasuryAction);
+            token_vault.owner = owner;
+            msg!("Treasury proposal {} executed: vault owner set to {}", treasury_proposal.id, owner);
+        }
+    }
+    Ok(())
+}
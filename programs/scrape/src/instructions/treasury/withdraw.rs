@@ -0,0 +1,77 @@
+use crate::error::ErrorCode;
+use crate::state::token::TokenVault;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::token_vault::transfer_tokens;
+
+#[derive(Accounts)]
+pub struct WithdrawTreasuryContext<'info> {
+    #[account(
+        mut,
+        seeds = [TokenVault::PREFIX.as_bytes(), token_vault.mint.as_ref()],
+        bump = token_vault.bump,
+        constraint = !token_vault.paused @ ErrorCode::VaultPaused,
+        constraint = token_vault.owner == signer.key() @ ErrorCode::UnauthorizedTreasury
+    )]
+    pub token_vault: Box<Account<'info, TokenVault>>,
+    #[account(address = token_vault.mint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = token_vault.token_account)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == token_vault.mint,
+    )]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub signer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraws treasury funds for a vault owned by a single key; multisig-owned vaults withdraw
+/// through an executed `TreasuryAction::Withdraw` proposal.
+#[inline(never)]
+pub fn withdraw(ctx: Context<WithdrawTreasuryContext>, amount: u64) -> Result<()> {
+    pay_from_treasury(
+        &mut ctx.accounts.token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_account,
+        &mut ctx.accounts.destination_token_account,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    msg!(
+        "Treasury withdrawal of {} to {} by owner: {}",
+        amount,
+        ctx.accounts.destination_token_account.key(),
+        ctx.accounts.signer.key()
+    );
+    Ok(())
+}
+
+/// Moves `amount` out of the treasury pool; escrow and bonus funds in the same token account are
+/// never touched.
+pub fn pay_from_treasury<'info>(
+    token_vault: &mut Account<'info, TokenVault>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault_token_account: &InterfaceAccount<'info, TokenAccount>,
+    destination_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    token_vault.debit_treasury(amount)?;
+
+    let vault_mint = token_vault.mint;
+    let bump = [token_vault.bump];
+    let seeds: &[&[u8]] = &[TokenVault::PREFIX.as_bytes(), vault_mint.as_ref(), &bump];
+    transfer_tokens(
+        token_program,
+        vault_token_account,
+        destination_token_account,
+        mint,
+        token_vault.to_account_info(),
+        &[seeds],
+        amount,
+    )?;
+    Ok(())
+}
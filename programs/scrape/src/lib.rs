@@ -2,20 +2,26 @@
 #![allow(ambiguous_glob_reexports)]
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
 
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 // Import error and instruction modules explicitly
 pub use error::ErrorCode;
 pub use instructions::*;
+pub use state::capability::{AsnClass, NodeCapabilities, TaskRequirements};
+pub use state::config::ConfigParams;
+pub use state::node_address::{ConnectionInfo, NodeAddress};
+pub use state::dataset_listing::DatasetLicense;
+pub use state::download_receipt::PaymentMethod;
+pub use state::reputation::ReputationWeights;
+pub use state::task::{DatasetManifest, StorageKind};
+pub use state::treasury::TreasuryAction;
 
-// Constants for the program
-pub const SCRAPE_MINT: &str = "6F2hasc11STQVPcZfX6E767wWV6TZXQRw74fAe11hCH3"; // This is the test mint address for POC
-pub const BONUS_RATE: u64 = 100; // Bonus reward rate
-pub const REPUTATION_THRESHOLD: u64 = 50;
+// Constants for the program (tunable parameters live in the `Config` account)
+pub const BPS_DENOMINATOR: u16 = 10_000;
 
 declare_id!("7pqme6UtiQshBaes6hQ2HkEwnwUph1JsEujZzKi9rmxU");
 
@@ -23,15 +29,81 @@ declare_id!("7pqme6UtiQshBaes6hQ2HkEwnwUph1JsEujZzKi9rmxU");
 pub mod scrape {
     use super::*;
 
+    // Config Instructions
+    pub fn init_config(ctx: Context<InitializeConfigContext>, params: ConfigParams) -> Result<()> {
+        config::init(ctx, params)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfigContext>, params: ConfigParams) -> Result<()> {
+        config::update(ctx, params)
+    }
+
+    pub fn transfer_admin(ctx: Context<UpdateConfigContext>, new_admin: Pubkey) -> Result<()> {
+        config::transfer_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdminContext>) -> Result<()> {
+        config::accept_admin(ctx)
+    }
+
     // Token Vault Instructions
-    pub fn init_token_vault(ctx: Context<InitializeTokenVaultContext>) -> Result<()> {
-        token_vault::init(ctx)?;
+    pub fn init_token_vault(ctx: Context<InitializeTokenVaultContext>, owner: Pubkey, rate_per_mb: u64) -> Result<()> {
+        token_vault::init(ctx, owner, rate_per_mb)?;
         Ok(())
     }
 
+    pub fn update_token_vault(ctx: Context<UpdateTokenVaultContext>, approved: bool, rate_per_mb: u64) -> Result<()> {
+        token_vault::update(ctx, approved, rate_per_mb)
+    }
+
+    pub fn reconcile_vault(ctx: Context<ReconcileVaultContext>) -> Result<()> {
+        token_vault::reconcile(ctx)
+    }
+
+    pub fn resume_vault(ctx: Context<ResumeVaultContext>) -> Result<()> {
+        token_vault::resume(ctx)
+    }
+
+    pub fn migrate_token_vault(ctx: Context<MigrateTokenVaultContext>) -> Result<()> {
+        token_vault::migrate(ctx)
+    }
+
+    pub fn migrate_task(ctx: Context<MigrateTaskContext>) -> Result<()> {
+        token_vault::migrate_task(ctx)
+    }
+
+    // Treasury Instructions
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasuryContext>, amount: u64) -> Result<()> {
+        treasury::withdraw(ctx, amount)
+    }
+
+    pub fn init_treasury_multisig(
+        ctx: Context<InitializeTreasuryMultisigContext>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        treasury::init_multisig(ctx, signers, threshold)
+    }
+
+    pub fn hand_over_vault(ctx: Context<HandOverVaultContext>) -> Result<()> {
+        treasury::hand_over_vault(ctx)
+    }
+
+    pub fn propose_treasury_action(ctx: Context<ProposeTreasuryActionContext>, action: TreasuryAction) -> Result<()> {
+        treasury::propose(ctx, action)
+    }
+
+    pub fn approve_treasury_proposal(ctx: Context<ApproveTreasuryProposalContext>) -> Result<()> {
+        treasury::approve(ctx)
+    }
+
+    pub fn execute_treasury_proposal(ctx: Context<ExecuteTreasuryProposalContext>) -> Result<()> {
+        treasury::execute(ctx)
+    }
+
     // Client Instructions
     pub fn create_client(ctx: Context<CreateClientContext>) -> Result<()> {
-        client::create(ctx);
+        client::create(ctx)?;
         Ok(())
     }
 
@@ -41,8 +113,11 @@ pub mod scrape {
     }
 
     // Endpoint Node Instructions
-    pub fn create_endpoint_node(ctx: Context<CreateEndpointNodeContext>) -> Result<()> {
-        endpoint_node::create(ctx)?;
+    pub fn create_endpoint_node(
+        ctx: Context<CreateEndpointNodeContext>,
+        encryption_key: [u8; 32],
+    ) -> Result<()> {
+        endpoint_node::create(ctx, encryption_key)?;
         Ok(())
     }
 
@@ -54,37 +129,123 @@ pub mod scrape {
     // Provider Node Instructions
     pub fn create_node(
         ctx: Context<CreateProviderNodeContext>,
-        ipv4: [u8; 4],
-        proxy_port: u16,
-        client_port: u16,
+        connection_commitment: [u8; 32],
         bandwidth_limit: u64,
+        stake_amount: u64,
+        capabilities: NodeCapabilities,
+        page_index: u32,
     ) -> Result<()> {
-        provider_node::create(ctx, ipv4, proxy_port, client_port, bandwidth_limit)
+        provider_node::create(ctx, connection_commitment, bandwidth_limit, stake_amount, capabilities, page_index)
     }
 
     pub fn node_registry_initialize(ctx: Context<NodeRegistryInitializeContext>) -> Result<()> {
         node_registry::noderegistryinitialize(ctx)
     }
 
+    pub fn register_node(ctx: Context<RegisterNodeContext>, page_index: u32) -> Result<()> {
+        node_registry::register(ctx, page_index)
+    }
+
+    pub fn deregister_node(ctx: Context<DeregisterNodeContext>) -> Result<()> {
+        node_registry::deregister(ctx)
+    }
+
+    pub fn compact_registry<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompactRegistryContext<'info>>,
+        target_index: u32,
+    ) -> Result<()> {
+        node_registry::compact(ctx, target_index)
+    }
+
+    pub fn migrate_node_registry(ctx: Context<MigrateNodeRegistryContext>) -> Result<()> {
+        node_registry::migrate(ctx)
+    }
+
     pub fn update_node(
         ctx: Context<UpdateProviderNodeContext>,
-        ipv4: [u8; 4],
-        proxy_port: u16,
-        client_port: u16,
+        connection_commitment: [u8; 32],
         bandwidth_limit: u64,
     ) -> Result<()> {
-        provider_node::update(ctx, ipv4, proxy_port, client_port, bandwidth_limit)
+        provider_node::update(ctx, connection_commitment, bandwidth_limit)
     }
 
     pub fn update_node_report(
         ctx: Context<UpdateProviderNodeReportContext>,
         bandwidth_used: u64,
-        reputation_increase: u64,
     ) -> Result<()> {
-        provider_node::update_report(ctx, bandwidth_used, reputation_increase)
+        provider_node::update_report(ctx, bandwidth_used)
+    }
+
+    pub fn migrate_node(ctx: Context<MigrateProviderNodeContext>) -> Result<()> {
+        provider_node::migrate(ctx)
+    }
+
+    pub fn set_node_capabilities(
+        ctx: Context<SetNodeCapabilitiesContext>,
+        capabilities: NodeCapabilities,
+    ) -> Result<()> {
+        provider_node::set_capabilities(ctx, capabilities)
+    }
+
+    pub fn pause_node(ctx: Context<SetNodeStatusContext>) -> Result<()> {
+        provider_node::pause(ctx)
+    }
+
+    pub fn resume_node(ctx: Context<SetNodeStatusContext>) -> Result<()> {
+        provider_node::resume(ctx)
+    }
+
+    pub fn close_node(ctx: Context<CloseProviderNodeContext>) -> Result<()> {
+        provider_node::close(ctx)
+    }
+
+    pub fn set_session_key(
+        ctx: Context<SetSessionKeyContext>,
+        session_key: Option<Pubkey>,
+        expires_at: i64,
+    ) -> Result<()> {
+        provider_node::set_session_key(ctx, session_key, expires_at)
+    }
+
+    // Reputation Config Instructions
+    pub fn init_reputation_config(
+        ctx: Context<InitializeReputationConfigContext>,
+        weights: ReputationWeights,
+    ) -> Result<()> {
+        reputation_config::init(ctx, weights)
+    }
+
+    pub fn update_reputation_config(
+        ctx: Context<UpdateReputationConfigContext>,
+        weights: ReputationWeights,
+    ) -> Result<()> {
+        reputation_config::update(ctx, weights)
+    }
+
+    // Randomness Feed Instructions
+    pub fn init_randomness_feed(ctx: Context<InitializeRandomnessFeedContext>) -> Result<()> {
+        randomness_feed::init(ctx)
+    }
+
+    pub fn publish_randomness(ctx: Context<PublishRandomnessContext>, value: [u8; 32]) -> Result<()> {
+        randomness_feed::publish(ctx, value)
+    }
+
+    // Stake Instructions
+    pub fn increase_stake(ctx: Context<IncreaseStakeContext>, amount: u64) -> Result<()> {
+        stake::increase(ctx, amount)
+    }
+
+    pub fn request_unstake(ctx: Context<RequestUnstakeContext>, amount: u64) -> Result<()> {
+        stake::request_unstake(ctx, amount)
+    }
+
+    pub fn withdraw_stake(ctx: Context<WithdrawStakeContext>) -> Result<()> {
+        stake::withdraw(ctx)
     }
 
     // Task Instructions
+    #[allow(clippy::too_many_arguments)]
     pub fn create_task(
         ctx: Context<CreateTaskContext>,
         url: String,
@@ -92,36 +253,152 @@ pub mod scrape {
         label: String,
         format: String,
         reward: u64,
+        deadline: i64,
+        allow_self_claim: bool,
+        requirements: TaskRequirements,
     ) -> Result<()> {
-        task::create(ctx, url, filter, label, format, reward)
+        task::create(ctx, url, filter, label, format, reward, deadline, allow_self_claim, requirements)
     }
 
     pub fn close_task(ctx: Context<CloseTaskContext>) -> Result<()> {
      task::close_task(ctx)
     }
 
+    pub fn cancel_task(ctx: Context<CancelTaskContext>) -> Result<()> {
+        task::cancel(ctx)
+    }
+
+    pub fn refund_expired_task(ctx: Context<RefundExpiredTaskContext>) -> Result<()> {
+        task::refund_expired(ctx)
+    }
+
     pub fn assign_task(ctx: Context<AssignTaskContext>) -> Result<()> {
         task::assign(ctx)
     }
 
+    pub fn select_provider<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SelectProviderContext<'info>>,
+    ) -> Result<()> {
+        task::select_provider(ctx)
+    }
+
+    pub fn reset_selection(ctx: Context<ResetSelectionContext>) -> Result<()> {
+        task::reset_selection(ctx)
+    }
+
+    pub fn share_connection(ctx: Context<ShareConnectionContext>, sealed_connection: Vec<u8>) -> Result<()> {
+        task::share_connection(ctx, sealed_connection)
+    }
+
     pub fn assign_task_by_endpoint(ctx: Context<AssignTaskByEndpointContext>) -> Result<()> {
         task::assign_task_by_endpoint(ctx)
     }
 
-    pub fn complete_task(ctx: Context<CompleteTaskContext>, ipfs_hash: String) -> Result<()> {
-        task::complete(ctx, ipfs_hash)
+    pub fn reclaim_stalled_task(ctx: Context<ReclaimStalledTaskContext>) -> Result<()> {
+        task::reclaim_stalled(ctx)
+    }
+
+    pub fn complete_task(ctx: Context<CompleteTaskContext>, manifest: DatasetManifest) -> Result<()> {
+        task::complete(ctx, manifest)
+    }
+
+    pub fn accept_result(ctx: Context<AcceptResultContext>) -> Result<()> {
+        task::accept_result(ctx)
+    }
+
+    pub fn reject_result(ctx: Context<RejectResultContext>) -> Result<()> {
+        task::reject_result(ctx)
+    }
+
+    pub fn finalize_result(ctx: Context<FinalizeResultContext>) -> Result<()> {
+        task::finalize_result(ctx)
+    }
+
+    pub fn finalize_rejection(ctx: Context<FinalizeRejectionContext>) -> Result<()> {
+        task::finalize_rejection(ctx)
     }
 
-    pub fn claim_task_rewards(ctx: Context<ClaimTaskRewardsContext>) -> Result<()> {
-        task::rewards(ctx)
+    // Bonus Instructions
+    pub fn fund_bonus_epoch(ctx: Context<FundBonusEpochContext>, epoch: u64, amount: u64) -> Result<()> {
+        bonus::fund(ctx, epoch, amount)
+    }
+
+    pub fn claim_bonus(ctx: Context<ClaimBonusContext>, epoch: u64) -> Result<()> {
+        bonus::claim(ctx, epoch)
+    }
+
+    pub fn sweep_bonus_epoch(ctx: Context<SweepBonusEpochContext>, epoch: u64, target_epoch: u64) -> Result<()> {
+        bonus::sweep(ctx, epoch, target_epoch)
+    }
+
+    // Arbiter Registry Instructions
+    pub fn init_arbiter_registry(
+        ctx: Context<InitializeArbiterRegistryContext>,
+        authority: Pubkey,
+        quorum: u8,
+    ) -> Result<()> {
+        arbiter_registry::init(ctx, authority, quorum)
+    }
+
+    pub fn add_arbiter(ctx: Context<UpdateArbiterRegistryContext>, arbiter: Pubkey) -> Result<()> {
+        arbiter_registry::add_arbiter(ctx, arbiter)
+    }
+
+    pub fn remove_arbiter(ctx: Context<UpdateArbiterRegistryContext>, arbiter: Pubkey) -> Result<()> {
+        arbiter_registry::remove_arbiter(ctx, arbiter)
+    }
+
+    pub fn set_arbiter_quorum(ctx: Context<UpdateArbiterRegistryContext>, quorum: u8) -> Result<()> {
+        arbiter_registry::set_quorum(ctx, quorum)
+    }
+
+    // Dispute Instructions
+    pub fn open_dispute(ctx: Context<OpenDisputeContext>, evidence_hash: [u8; 32]) -> Result<()> {
+        dispute::open(ctx, evidence_hash)
+    }
+
+    pub fn submit_dispute_evidence(
+        ctx: Context<SubmitDisputeEvidenceContext>,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        dispute::submit_evidence(ctx, evidence_hash)
+    }
+
+    pub fn vote_dispute(ctx: Context<VoteDisputeContext>, provider_share_bps: u16) -> Result<()> {
+        dispute::vote(ctx, provider_share_bps)
+    }
+
+    pub fn settle_dispute(ctx: Context<SettleDisputeContext>) -> Result<()> {
+        dispute::settle(ctx)
     }
 
     // Dataset Instructions
-    pub fn download_dataset(ctx: Context<DownloadDatasetContext>) -> Result<()> {
-        dataset::download(ctx)
+    pub fn download_dataset(ctx: Context<DownloadDatasetContext>, payment: PaymentMethod) -> Result<()> {
+        dataset::download(ctx, payment)
     }
 
     pub fn preview_dataset(ctx: Context<PreviewDatasetContext>) -> Result<()> {
         dataset::preview(ctx)
     }
+
+    pub fn list_dataset(
+        ctx: Context<ListDatasetContext>,
+        price: u64,
+        license: DatasetLicense,
+    ) -> Result<()> {
+        dataset::list(ctx, price, license)
+    }
+
+    pub fn update_dataset_listing(
+        ctx: Context<UpdateListingContext>,
+        price: u64,
+        license: DatasetLicense,
+        active: bool,
+    ) -> Result<()> {
+        dataset::update_listing(ctx, price, license, active)
+    }
+
+    pub fn purchase_dataset(ctx: Context<PurchaseDatasetContext>, max_price: u64) -> Result<()> {
+        dataset::purchase(ctx, max_price)
+    }
 }
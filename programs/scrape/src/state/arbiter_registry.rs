@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct ArbiterRegistry {
+    pub bump: u8,               // Bump seed for PDA
+    pub authority: Pubkey,      // Governance authority allowed to manage arbiters
+    pub quorum: u8,             // Votes required before a dispute can be settled
+    pub arbiters: Vec<Pubkey>,  // Arbiters allowed to vote on disputes
+}
+
+impl ArbiterRegistry {
+    pub const PREFIX: &'static str = "ARBITER_REGISTRY";
+
+    pub const MAX_ARBITERS: usize = 16;
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<Pubkey>() + // authority
+        std::mem::size_of::<u8>() + // quorum
+        4 + Self::MAX_ARBITERS * std::mem::size_of::<Pubkey>() + // arbiters (Vec<Pubkey>)
+        64; // padding
+
+    pub fn is_arbiter(&self, key: &Pubkey) -> bool {
+        self.arbiters.contains(key)
+    }
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct BonusEpoch {
+    pub bump: u8,               // Bump seed for PDA
+    pub epoch: u64,             // Epoch number (unix timestamp / `bonus_epoch_length`)
+    pub funded: u64,            // $SCRAPE deposited into this epoch's pool
+    pub total_contribution: u64, // Sum of all nodes' verified contributions (MB of accepted work)
+    pub claimed: u64,           // $SCRAPE already paid out of the pool
+    pub swept: bool,            // Whether the unclaimed remainder has moved to a later epoch
+}
+
+impl BonusEpoch {
+    pub const PREFIX: &'static str = "BONUS_EPOCH";
+
+    // Epochs after its end during which an epoch can only be claimed, not swept
+    pub const CLAIM_WINDOW_EPOCHS: u64 = 4;
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<u64>() + // epoch
+        std::mem::size_of::<u64>() + // funded
+        std::mem::size_of::<u64>() + // total_contribution
+        std::mem::size_of::<u64>() + // claimed
+        std::mem::size_of::<bool>() + // swept
+        31; // padding
+
+    /// Epoch containing `now` for epochs of `length` seconds.
+    pub fn at(now: i64, length: i64) -> u64 {
+        now.max(0).checked_div(length).unwrap_or(0) as u64
+    }
+
+    /// The epoch in progress, used to derive the `BonusEpoch` that accepted work is credited to.
+    pub fn current(length: i64) -> Result<u64> {
+        Ok(Self::at(Clock::get()?.unix_timestamp, length))
+    }
+
+    /// The node's pro-rata share of the funded pool.
+    pub fn share(&self, contribution: u64) -> Result<u64> {
+        if self.total_contribution == 0 {
+            return Ok(0);
+        }
+        let share = (self.funded as u128)
+            .checked_mul(contribution as u128)
+            .and_then(|amount| amount.checked_div(self.total_contribution as u128))
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(share as u64)
+    }
+
+    /// Funds no claim has paid out yet.
+    pub fn unclaimed(&self) -> u64 {
+        self.funded.saturating_sub(self.claimed)
+    }
+
+    /// Whether the remainder can be swept during `current`: once the claim window has passed, or
+    /// as soon as the epoch has ended if no work was credited to it.
+    pub fn is_sweepable(&self, current: u64) -> bool {
+        !self.swept
+            && self.epoch < current
+            && (self.total_contribution == 0
+                || current > self.epoch.saturating_add(Self::CLAIM_WINDOW_EPOCHS))
+    }
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct BonusClaim {
+    pub bump: u8,               // Bump seed for PDA
+    pub node: Pubkey,           // ProviderNode owner
+    pub epoch: u64,             // Epoch the contribution was made in
+    pub contribution: u64,      // Node's verified contribution in the epoch (MB of accepted work)
+    pub claimed: bool,          // Whether the node's share has been paid out
+}
+
+impl BonusClaim {
+    pub const PREFIX: &'static str = "BONUS_CLAIM";
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<Pubkey>() + // node
+        std::mem::size_of::<u64>() + // epoch
+        std::mem::size_of::<u64>() + // contribution
+        std::mem::size_of::<bool>() + // claimed
+        32; // padding
+}
+
+/// Credits `amount` MB of accepted work by `node` to the current epoch's pool.
+pub fn record_contribution(
+    bonus_epoch: &mut Account<BonusEpoch>,
+    bonus_epoch_bump: u8,
+    bonus_claim: &mut Account<BonusClaim>,
+    bonus_claim_bump: u8,
+    node: Pubkey,
+    epoch: u64,
+    amount: u64,
+) -> Result<()> {
+    // Both accounts may have just been created by this contribution; their identity fields follow
+    // from the PDA seeds, so setting them again is a no-op
+    bonus_epoch.bump = bonus_epoch_bump;
+    bonus_epoch.epoch = epoch;
+    bonus_claim.bump = bonus_claim_bump;
+    bonus_claim.node = node;
+    bonus_claim.epoch = epoch;
+
+    bonus_epoch.total_contribution = bonus_epoch
+        .total_contribution
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    bonus_claim.contribution = bonus_claim
+        .contribution
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sweepable_after_claim_window() {
+        let epoch = BonusEpoch { epoch: 10, funded: 1_000, total_contribution: 50, claimed: 400, ..Default::default() };
+
+        assert_eq!(epoch.unclaimed(), 600);
+        assert!(!epoch.is_sweepable(10));
+        assert!(!epoch.is_sweepable(10 + BonusEpoch::CLAIM_WINDOW_EPOCHS));
+        assert!(epoch.is_sweepable(11 + BonusEpoch::CLAIM_WINDOW_EPOCHS));
+    }
+
+    #[test]
+    fn sweepable_once_ended_without_contributions() {
+        let epoch = BonusEpoch { epoch: 10, funded: 1_000, ..Default::default() };
+
+        assert!(!epoch.is_sweepable(10));
+        assert!(epoch.is_sweepable(11));
+    }
+
+    #[test]
+    fn swept_epoch_is_not_sweepable_again() {
+        let epoch = BonusEpoch { epoch: 10, funded: 1_000, swept: true, ..Default::default() };
+
+        assert!(!epoch.is_sweepable(20));
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum AsnClass {
+    #[default]
+    Unknown,        // Not declared
+    Residential,    // Consumer ISP
+    Mobile,         // Cellular carrier
+    Datacenter,     // Hosting or cloud provider
+}
+
+/// Output formats as bit flags, combined in `NodeCapabilities::formats` and `TaskRequirements::formats`.
+pub mod output_format {
+    pub const JSONL: u8 = 1 << 0;
+    pub const JSON: u8 = 1 << 1;
+    pub const CSV: u8 = 1 << 2;
+    pub const PARQUET: u8 = 1 << 3;
+    pub const ALL: u8 = JSONL | JSON | CSV | PARQUET;
+}
+
+/// Capability profile published by a provider node.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default)]
+pub struct NodeCapabilities {
+    pub country: [u8; 2],           // ISO 3166-1 alpha-2 country code (e.g. "DE")
+    pub region: [u8; 3],            // ISO 3166-2 subdivision suffix, zero-padded (e.g. "BY\0"), zeros if undeclared
+    pub asn_class: AsnClass,        // Kind of network the node's IP belongs to
+    pub ipv6: bool,                 // Node can egress over IPv6
+    pub js_rendering: bool,         // Node can render JavaScript-driven pages
+    pub max_concurrent_tasks: u16,  // Assigned tasks the node accepts at once
+    pub formats: u8,                // Producible output formats (`output_format` flags)
+}
+
+impl NodeCapabilities {
+    pub const SIZE: usize = 2 + 3 + 1 + 1 + 1 + 2 + 1;
+
+    pub fn is_valid(&self) -> bool {
+        is_country_code(&self.country)
+            && is_region_code(&self.region)
+            && self.max_concurrent_tasks > 0
+            && self.formats != 0
+            && self.formats & !output_format::ALL == 0
+    }
+}
+
+/// Node capabilities a task needs. Unset fields accept any node.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default)]
+pub struct TaskRequirements {
+    pub country: Option<[u8; 2]>,   // Required ISO 3166-1 alpha-2 country
+    pub region: Option<[u8; 3]>,    // Required ISO 3166-2 subdivision within `country`
+    pub asn_class: Option<AsnClass>, // Required network kind (e.g. residential only)
+    pub ipv6: bool,                 // Requires IPv6 egress
+    pub js_rendering: bool,         // Requires JavaScript rendering
+    pub formats: u8,                // Output formats the node must all support (`output_format` flags)
+}
+
+impl TaskRequirements {
+    pub const SIZE: usize = (1 + 2) + (1 + 3) + (1 + 1) + 1 + 1 + 1;
+
+    pub fn is_valid(&self) -> bool {
+        self.country.as_ref().is_none_or(is_country_code)
+            && self.region.as_ref().is_none_or(is_region_code)
+            && (self.region.is_none() || self.country.is_some())
+            && self.formats & !output_format::ALL == 0
+    }
+
+    pub fn is_met_by(&self, capabilities: &NodeCapabilities) -> bool {
+        self.country.is_none_or(|country| country == capabilities.country)
+            && self.region.is_none_or(|region| region == capabilities.region)
+            && self.asn_class.is_none_or(|class| class == capabilities.asn_class)
+            && (!self.ipv6 || capabilities.ipv6)
+            && (!self.js_rendering || capabilities.js_rendering)
+            && capabilities.formats & self.formats == self.formats
+    }
+}
+
+fn is_country_code(code: &[u8; 2]) -> bool {
+    code.iter().all(u8::is_ascii_uppercase)
+}
+
+fn is_region_code(code: &[u8; 3]) -> bool {
+    // Undeclared, or 1-3 uppercase alphanumerics followed by zero padding
+    let len = code.iter().position(|b| *b == 0).unwrap_or(code.len());
+    code[..len].iter().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        && code[len..].iter().all(|b| *b == 0)
+}
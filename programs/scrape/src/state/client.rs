@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::download_receipt::PaymentMethod;
 
 #[account]
 #[derive(Default, Debug)]
@@ -6,6 +8,13 @@ pub struct Client {
     pub bump: u8,           // Bump seed for PDA
     pub owner: Pubkey,      // User who owns this state
     pub task_counter: u64,  // Local counter for user's tasks
+    pub free_allowance_used: u64, // Free download allowance consumed this period (MB)
+    pub allowance_period_start: i64, // Unix timestamp the current allowance period started
+    pub total_spent: u64,   // $SCRAPE paid out of task escrows and for downloads
+    pub total_spent_lamports: u64, // Lamports paid for downloads
+    pub bytes_downloaded: u64, // Dataset bytes downloaded, free allowance included
+    pub open_tasks: u32,    // Tasks that are not yet completed, cancelled or refunded
+    pub completed_tasks: u64, // Tasks that reached Completed
 }
 
 impl Client {
@@ -15,5 +24,90 @@ impl Client {
         std::mem::size_of::<u8>() + // bump
         std::mem::size_of::<Pubkey>() + // owner
         std::mem::size_of::<u64>() + // task_counter
-        64; // padding
-}
\ No newline at end of file
+        std::mem::size_of::<u64>() + // free_allowance_used
+        std::mem::size_of::<i64>() + // allowance_period_start
+        std::mem::size_of::<u64>() + // total_spent
+        std::mem::size_of::<u64>() + // total_spent_lamports
+        std::mem::size_of::<u64>() + // bytes_downloaded
+        std::mem::size_of::<u32>() + // open_tasks
+        std::mem::size_of::<u64>() + // completed_tasks
+        28; // padding
+
+    /// Starts a new allowance period if the current one has ended. Periods stay aligned to the
+    /// first one, so skipped periods are not carried over. Returns whether a rollover happened.
+    pub fn roll_period(&mut self, period: i64, now: i64) -> bool {
+        if period <= 0 || now < self.allowance_period_start.saturating_add(period) {
+            return false;
+        }
+
+        let elapsed = now - self.allowance_period_start;
+        self.allowance_period_start += elapsed - elapsed % period;
+        self.free_allowance_used = 0;
+        true
+    }
+
+    /// Draws `size` MB from the free allowance, starting a new period if the current one has ended.
+    /// Returns the MB that are not covered and must be paid for.
+    pub fn consume_free_allowance(&mut self, size: u64, allowance: u64, period: i64, now: i64) -> u64 {
+        self.roll_period(period, now);
+
+        let remaining = allowance.saturating_sub(self.free_allowance_used);
+        let covered = size.min(remaining);
+        self.free_allowance_used += covered;
+        size - covered
+    }
+
+    /// Records a download of `bytes` that cost `amount` in the `payment` currency.
+    pub fn record_download(&mut self, bytes: u64, amount: u64, payment: PaymentMethod) -> Result<()> {
+        let spent = match payment {
+            PaymentMethod::Sol => &mut self.total_spent_lamports,
+            PaymentMethod::Scrape => &mut self.total_spent,
+        };
+        *spent = spent.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        self.bytes_downloaded = self.bytes_downloaded.checked_add(bytes).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn open_task(&mut self) -> Result<()> {
+        self.open_tasks = self.open_tasks.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Closes out an open task that paid `spent` $SCRAPE to its provider.
+    pub fn close_task(&mut self, spent: u64, completed: bool) -> Result<()> {
+        // Tasks opened before the ledger existed were never counted
+        self.open_tasks = self.open_tasks.saturating_sub(1);
+        self.total_spent = self.total_spent.checked_add(spent).ok_or(ErrorCode::MathOverflow)?;
+        if completed {
+            self.completed_tasks = self.completed_tasks.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roll_period_keeps_periods_aligned() {
+        let mut client = Client { allowance_period_start: 1_000, free_allowance_used: 40, ..Default::default() };
+
+        assert!(!client.roll_period(100, 1_099));
+        assert_eq!(client.free_allowance_used, 40);
+
+        assert!(client.roll_period(100, 1_350));
+        assert_eq!(client.allowance_period_start, 1_300);
+        assert_eq!(client.free_allowance_used, 0);
+    }
+
+    #[test]
+    fn free_allowance_resets_with_the_period() {
+        let mut client = Client { allowance_period_start: 0, ..Default::default() };
+
+        assert_eq!(client.consume_free_allowance(30, 50, 100, 10), 0);
+        assert_eq!(client.consume_free_allowance(30, 50, 100, 20), 10);
+        assert_eq!(client.consume_free_allowance(30, 50, 100, 120), 0);
+        assert_eq!(client.free_allowance_used, 30);
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default)]
+pub struct ConfigParams {
+    pub scrape_mint: Pubkey,            // $SCRAPE mint used for stakes, bonuses and dataset payments
+    pub bonus_epoch_length: i64,        // Seconds per bonus emission epoch
+    pub reputation_threshold: u64,      // Reputation required to claim bonus rewards
+    pub free_threshold: u64,            // Free dataset download allowance per client per period (MB)
+    pub rate_per_mb_lamports: u64,      // Download price above the free allowance (lamports per MB)
+    pub rate_per_mb_tokens: u64,        // Download price above the free allowance ($SCRAPE per MB)
+    pub allowance_period: i64,          // Seconds before a client's free allowance resets
+    pub fee_receiver: Pubkey,           // Account receiving SOL download payments
+    pub protocol_fee_bps: u16,          // Protocol fee taken from payments (basis points)
+    pub market_provider_bps: u16,       // Provider node's share of dataset sales (basis points)
+    pub assignment_timeout: i64,        // Seconds an assigned task may run before it can be reclaimed
+    pub review_window: i64,             // Seconds the owner has to review a submitted result
+    pub unstake_cooldown: i64,          // Seconds between an unstake request and withdrawal
+    pub min_stake: u64,                 // Minimum $SCRAPE a provider node must keep staked to stay active
+    pub stall_slash_bps: u16,           // Share of stake slashed when an assignment is reclaimed
+    pub dispute_slash_bps: u16,         // Share of stake slashed when a provider loses a dispute
+    pub randomness_feed: Option<Pubkey>, // RandomnessFeed used for provider selection (slot hashes if unset)
+    pub task_fee_bps: u16,              // Protocol fee taken from task rewards (basis points)
+    pub dispute_timeout: i64,           // Seconds after which an unsettled dispute can be settled without quorum
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct Config {
+    pub bump: u8,               // Bump seed for PDA
+    pub admin: Pubkey,          // Authority allowed to update the config
+    pub pending_admin: Option<Pubkey>, // Proposed admin awaiting acceptance
+    pub params: ConfigParams,   // Tunable program parameters
+}
+
+impl Config {
+    pub const PREFIX: &'static str = "CONFIG";
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<Pubkey>() + // admin
+        (1 + 32) + // pending_admin (Option<Pubkey>)
+        std::mem::size_of::<Pubkey>() + // params.scrape_mint
+        std::mem::size_of::<i64>() + // params.bonus_epoch_length
+        3 * std::mem::size_of::<u64>() + // params.reputation_threshold, free_threshold, rate_per_mb_lamports
+        std::mem::size_of::<u64>() + // params.rate_per_mb_tokens
+        std::mem::size_of::<i64>() + // params.allowance_period
+        std::mem::size_of::<Pubkey>() + // params.fee_receiver
+        std::mem::size_of::<u16>() + // params.protocol_fee_bps
+        std::mem::size_of::<u16>() + // params.market_provider_bps
+        3 * std::mem::size_of::<i64>() + // params.assignment_timeout, review_window, unstake_cooldown
+        std::mem::size_of::<u64>() + // params.min_stake
+        2 * std::mem::size_of::<u16>() + // params.stall_slash_bps, dispute_slash_bps
+        (1 + 32) + // params.randomness_feed (Option<Pubkey>)
+        std::mem::size_of::<u16>() + // params.task_fee_bps
+        std::mem::size_of::<i64>() + // params.dispute_timeout
+        118; // padding for future parameters
+}
+
+impl ConfigParams {
+    /// Fee owed to the treasury on a task reward of `amount`.
+    pub fn task_fee(&self, amount: u64) -> Result<u64> {
+        Ok((amount as u128)
+            .checked_mul(self.task_fee_bps as u128)
+            .and_then(|fee| fee.checked_div(crate::BPS_DENOMINATOR as u128))
+            .ok_or(crate::error::ErrorCode::MathOverflow)? as u64)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.reputation_threshold > 0
+            && (self.protocol_fee_bps as u32 + self.market_provider_bps as u32) <= crate::BPS_DENOMINATOR as u32
+            && self.task_fee_bps <= crate::BPS_DENOMINATOR
+            && self.stall_slash_bps <= crate::BPS_DENOMINATOR
+            && self.dispute_slash_bps <= crate::BPS_DENOMINATOR
+            && self.assignment_timeout > 0
+            && self.review_window > 0
+            && self.dispute_timeout > 0
+            && self.allowance_period > 0
+            // A stalled assignment and the review of a submitted result must both end within the
+            // cooldown, so that stake cannot leave while it can still be slashed
+            && self.assignment_timeout
+                .checked_add(self.review_window)
+                .is_some_and(|slashable| self.unstake_cooldown >= slashable)
+            && self.bonus_epoch_length > 0
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum DatasetLicense {
+    #[default]
+    NonCommercial,  // Research and personal use only
+    Commercial,     // Commercial use permitted
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct DatasetListing {
+    pub bump: u8,               // Bump seed for PDA
+    pub task: Pubkey,           // Completed task whose dataset is for sale
+    pub seller: Pubkey,         // Task owner receiving the sale proceeds
+    pub provider: Pubkey,       // Provider node owner that produced the dataset
+    pub price: u64,             // Price per purchase in $SCRAPE
+    pub license: DatasetLicense, // License granted to buyers
+    pub active: bool,           // Whether the dataset can currently be purchased
+    pub sales: u64,             // Number of purchases
+}
+
+impl DatasetListing {
+    pub const PREFIX: &'static str = "DATASET_LISTING";
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<Pubkey>() + // task
+        std::mem::size_of::<Pubkey>() + // seller
+        std::mem::size_of::<Pubkey>() + // provider
+        std::mem::size_of::<u64>() + // price
+        1 + // license (DatasetLicense enum)
+        std::mem::size_of::<bool>() + // active
+        std::mem::size_of::<u64>() + // sales
+        32; // padding
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct DatasetAccess {
+    pub bump: u8,               // Bump seed for PDA
+    pub task: Pubkey,           // Task whose dataset was purchased
+    pub buyer: Pubkey,          // Client granted access
+    pub price_paid: u64,        // $SCRAPE paid for access
+    pub license: DatasetLicense, // License granted at purchase time
+    pub purchased_at: i64,      // Unix timestamp of the purchase
+}
+
+impl DatasetAccess {
+    pub const PREFIX: &'static str = "DATASET_ACCESS";
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<Pubkey>() + // task
+        std::mem::size_of::<Pubkey>() + // buyer
+        std::mem::size_of::<u64>() + // price_paid
+        1 + // license (DatasetLicense enum)
+        std::mem::size_of::<i64>() + // purchased_at
+        32; // padding
+}
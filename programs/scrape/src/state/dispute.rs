@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use crate::state::arbiter_registry::ArbiterRegistry;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default)]
+pub enum DisputeStatus {
+    #[default]
+    Open,       // Waiting for evidence and arbiter votes
+    Settled,    // Escrow has been split between client and provider
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default)]
+pub struct ArbiterVote {
+    pub arbiter: Pubkey,        // Arbiter who cast the vote
+    pub provider_share_bps: u16, // Share of the escrow awarded to the provider (basis points)
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct Dispute {
+    pub bump: u8,               // Bump seed for PDA
+    pub task: Pubkey,           // Disputed task
+    pub client: Pubkey,         // Task owner
+    pub provider: Pubkey,       // Owner of the node that submitted the result
+    pub client_evidence: [u8; 32],   // Hash of the client's evidence bundle
+    pub provider_evidence: [u8; 32], // Hash of the provider's evidence bundle (zeroed until submitted)
+    pub status: DisputeStatus,  // Dispute status
+    pub votes: Vec<ArbiterVote>, // Votes cast so far
+    pub provider_share_bps: u16, // Final provider share, set on settlement
+    pub opened_at: i64,         // Unix timestamp the dispute was opened
+    pub settled_at: i64,        // Unix timestamp the dispute was settled
+    pub opened_by: Pubkey,      // Party that opened the dispute and paid its rent
+}
+
+impl Dispute {
+    pub const PREFIX: &'static str = "DISPUTE";
+
+    // Arbiters reached no verdict in time, so neither party is held at fault
+    pub const TIMEOUT_PROVIDER_SHARE_BPS: u16 = 5_000;
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<Pubkey>() + // task
+        std::mem::size_of::<Pubkey>() + // client
+        std::mem::size_of::<Pubkey>() + // provider
+        32 + // client_evidence
+        32 + // provider_evidence
+        1 + // status (DisputeStatus enum)
+        4 + ArbiterRegistry::MAX_ARBITERS * (32 + 2) + // votes (Vec<ArbiterVote>)
+        std::mem::size_of::<u16>() + // provider_share_bps
+        std::mem::size_of::<i64>() + // opened_at
+        std::mem::size_of::<i64>() + // settled_at
+        std::mem::size_of::<Pubkey>() + // opened_by
+        32; // padding
+
+    /// Account the dispute's rent is returned to. Disputes opened before `opened_by` existed
+    /// were always opened by the client.
+    pub fn rent_payer(&self) -> Pubkey {
+        if self.opened_by == Pubkey::default() {
+            self.client
+        } else {
+            self.opened_by
+        }
+    }
+
+    /// Whether the dispute has waited `timeout` seconds for quorum. A zero timeout, as left by
+    /// configs written before the parameter existed, never expires.
+    pub fn has_timed_out(&self, now: i64, timeout: i64) -> bool {
+        timeout > 0 && self.opened_at.checked_add(timeout).is_some_and(|expires_at| now > expires_at)
+    }
+
+    pub fn has_voted(&self, arbiter: &Pubkey) -> bool {
+        self.votes.iter().any(|vote| vote.arbiter == *arbiter)
+    }
+
+    /// Number of votes from arbiters still in `registry` and the average provider share they
+    /// awarded. Votes of arbiters removed since voting no longer count.
+    pub fn tally(&self, registry: &ArbiterRegistry) -> (usize, u16) {
+        let (count, total) = self
+            .votes
+            .iter()
+            .filter(|vote| registry.is_arbiter(&vote.arbiter))
+            .fold((0u64, 0u64), |(count, total), vote| (count + 1, total + vote.provider_share_bps as u64));
+        if count == 0 {
+            return (0, 0);
+        }
+        (count as usize, (total / count) as u16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tally_ignores_votes_of_removed_arbiters() {
+        let kept = Pubkey::new_unique();
+        let removed = Pubkey::new_unique();
+        let registry = ArbiterRegistry {
+            arbiters: vec![kept],
+            ..Default::default()
+        };
+        let dispute = Dispute {
+            votes: vec![
+                ArbiterVote { arbiter: kept, provider_share_bps: 6_000 },
+                ArbiterVote { arbiter: removed, provider_share_bps: 0 },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(dispute.tally(&registry), (1, 6_000));
+    }
+
+    #[test]
+    fn dispute_times_out_only_with_a_configured_timeout() {
+        let dispute = Dispute { opened_at: 1_000, ..Default::default() };
+
+        assert!(!dispute.has_timed_out(1_100, 100));
+        assert!(dispute.has_timed_out(1_101, 100));
+        assert!(!dispute.has_timed_out(i64::MAX, 0));
+    }
+
+    #[test]
+    fn rent_returns_to_the_party_that_opened_the_dispute() {
+        let client = Pubkey::new_unique();
+        let provider = Pubkey::new_unique();
+        let legacy = Dispute { client, provider, ..Default::default() };
+        let by_provider = Dispute { client, provider, opened_by: provider, ..Default::default() };
+
+        assert_eq!(legacy.rent_payer(), client);
+        assert_eq!(by_provider.rent_payer(), provider);
+    }
+}
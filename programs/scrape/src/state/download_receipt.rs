@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum PaymentMethod {
+    #[default]
+    Sol,        // Lamports sent to the configured fee receiver
+    Scrape,     // $SCRAPE sent to the token vault
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct DownloadReceipt {
+    pub bump: u8,               // Bump seed for PDA
+    pub task: Pubkey,           // Task whose dataset was paid for
+    pub buyer: Pubkey,          // Client allowed to fetch the dataset
+    pub amount: u64,            // Amount paid (lamports or $SCRAPE, see `payment`)
+    pub payment: PaymentMethod, // Currency of `amount`
+    pub slot: u64,              // Slot the payment landed in
+}
+
+impl DownloadReceipt {
+    pub const PREFIX: &'static str = "DOWNLOAD_RECEIPT";
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<Pubkey>() + // task
+        std::mem::size_of::<Pubkey>() + // buyer
+        std::mem::size_of::<u64>() + // amount
+        1 + // payment (PaymentMethod enum)
+        std::mem::size_of::<u64>() + // slot
+        32; // padding
+}
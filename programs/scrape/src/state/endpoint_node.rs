@@ -5,6 +5,7 @@ use anchor_lang::prelude::*;
 pub struct EndpointNode {
     pub bump: u8,           // Bump seed for PDA
     pub owner: Pubkey,      // Node operator's public key
+    pub encryption_key: [u8; 32], // x25519 public key providers seal their connection info to
 }
 
 impl EndpointNode {
@@ -13,5 +14,6 @@ impl EndpointNode {
     pub const SIZE: usize = 8 + // Discriminator
         std::mem::size_of::<u8>() + // bump
         std::mem::size_of::<Pubkey>() + // owner
+        32 + // encryption_key
         64; // padding
 }
\ No newline at end of file
@@ -3,4 +3,15 @@ pub mod provider_node;
 pub mod client;
 pub mod task;
 pub mod endpoint_node;
-pub mod node_registry;
\ No newline at end of file
+pub mod node_registry;
+pub mod arbiter_registry;
+pub mod dispute;
+pub mod reputation;
+pub mod config;
+pub mod download_receipt;
+pub mod dataset_listing;
+pub mod randomness;
+pub mod capability;
+pub mod node_address;
+pub mod bonus;
+pub mod treasury;
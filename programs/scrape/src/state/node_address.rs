@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// A network address a provider node can be reached at.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub enum NodeAddress {
+    V4([u8; 4]),        // IPv4 address
+    V6([u8; 16]),       // IPv6 address
+    Hostname(String),   // DNS name, e.g. for dynamic DNS
+}
+
+/// How to reach a provider node. Never stored or logged on-chain in plaintext: nodes publish a
+/// commitment to it on their ProviderNode, the SHA-256 of its Borsh encoding followed by a secret
+/// 32-byte salt, and deliver the info itself per task, sealed to the endpoint node's x25519 key
+/// (see `share_connection`). The program only defines the layout; endpoints check the commitment
+/// and the addresses off-chain after unsealing.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct ConnectionInfo {
+    pub addresses: Vec<NodeAddress>, // Addresses the node is reachable at
+    pub proxy_port: u16,            // Port for proxy requests
+    pub client_port: u16,           // Port for client communication
+}
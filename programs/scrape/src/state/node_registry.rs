@@ -1,26 +1,84 @@
 use anchor_lang::prelude::*;
 
+/// Registry header. Provider nodes are listed in fixed-size `RegistryPage` accounts indexed
+/// `0..page_count`, so registration never reallocs and the registry can grow without bound.
 #[account]
 #[derive(Default, Debug)]
 pub struct NodeRegistry {
     pub bump: u8,               // Bump seed for PDA
-    pub nodes: Vec<Pubkey>,     // List of ProviderNode owner public keys
+    pub page_count: u32,        // Number of allocated pages
+    pub node_count: u32,        // Number of registered nodes across all pages
+    pub epoch: u64,             // Bumped when compaction moves nodes between pages
 }
 
 impl NodeRegistry {
     pub const PREFIX: &'static str = "NODE_REGISTRY";
 
-    // Initial space allocation: discriminator + bump + Vec<Pubkey>
-    // We allocate space for the discriminator, bump, and a Vec with initial capacity
-    pub const BASE_SIZE: usize = 8 + // Discriminator
+    pub const SIZE: usize = 8 + // Discriminator
         std::mem::size_of::<u8>() + // bump
-        4; // Vec length prefix (u32)
+        std::mem::size_of::<u32>() + // page_count
+        std::mem::size_of::<u32>() + // node_count
+        std::mem::size_of::<u64>() + // epoch
+        24; // padding
 
-    // Space per node entry (each Pubkey is 32 bytes)
-    pub const NODE_ENTRY_SIZE: usize = std::mem::size_of::<Pubkey>();
+    /// Empty slots across all allocated pages.
+    pub fn free_slots(&self) -> u32 {
+        (self.page_count * RegistryPage::CAPACITY as u32).saturating_sub(self.node_count)
+    }
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct RegistryPage {
+    pub bump: u8,               // Bump seed for PDA
+    pub index: u32,             // Position of the page in the registry
+    pub count: u16,             // Occupied slots
+    pub slots: [Pubkey; 16],    // ProviderNode owners, `Pubkey::default()` marks a free slot
+}
+
+impl RegistryPage {
+    pub const PREFIX: &'static str = "NODE_REGISTRY_PAGE";
+
+    // Sized so a whole page of nodes fits in one transaction alongside the selection accounts
+    pub const CAPACITY: usize = 16;
 
-    // Calculate total space needed for a given number of nodes
-    pub fn calculate_size(num_nodes: usize) -> usize {
-        Self::BASE_SIZE + (num_nodes * Self::NODE_ENTRY_SIZE)
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<u32>() + // index
+        std::mem::size_of::<u16>() + // count
+        Self::CAPACITY * std::mem::size_of::<Pubkey>() + // slots
+        16; // padding
+
+    pub fn is_full(&self) -> bool {
+        self.count as usize >= Self::CAPACITY
     }
-}
\ No newline at end of file
+
+    /// Occupied slots in slot order.
+    pub fn nodes(&self) -> impl Iterator<Item = &Pubkey> {
+        self.slots.iter().filter(|slot| **slot != Pubkey::default())
+    }
+
+    /// Places `owner` in the first free slot. Returns false if the page is full.
+    pub fn insert(&mut self, owner: Pubkey) -> bool {
+        match self.slots.iter_mut().find(|slot| **slot == Pubkey::default()) {
+            Some(slot) => {
+                *slot = owner;
+                self.count += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Frees the slot holding `owner`. Returns false if it is not on this page.
+    pub fn remove(&mut self, owner: &Pubkey) -> bool {
+        match self.slots.iter_mut().find(|slot| **slot == *owner) {
+            Some(slot) => {
+                *slot = Pubkey::default();
+                self.count -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -1,37 +1,283 @@
 use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::events::ReputationChanged;
+use crate::state::capability::{NodeCapabilities, TaskRequirements};
+use crate::state::reputation::{decay, ReputationEvent, ReputationWeights};
 
 #[account]
 #[derive(Default, Debug)]
 pub struct ProviderNode {
     pub bump: u8,               // Bump seed for PDA
     pub owner: Pubkey,          // Node operator's public key
-    pub ipv4: [u8; 4],          // IPv4 address of the node (e.g., [192, 168, 1, 1])
-    pub proxy_port: u16,        // Port for proxy requests
-    pub client_port: u16,       // Port for client communication
+    pub legacy_ipv4: [u8; 4],   // Former plaintext IPv4, kept for layout compatibility and zeroed by `migrate_node`
+    pub legacy_proxy_port: u16, // Former plaintext proxy port, zeroed by `migrate_node`
+    pub legacy_client_port: u16, // Former plaintext client port, zeroed by `migrate_node`
     pub bandwidth_limit: u64,   // Max bandwidth (MB/hour)
-    pub bandwidth_used: u64,    // Bandwidth used (MB)
-    pub reputation: u64,        // Reputation score
-    pub rewards: u64,           // Earned rewards (in $SCRAPE tokens)
+    pub bandwidth_used: u64,    // Lifetime bandwidth used (MB)
+    pub reputation: u64,        // Decayed reputation score as of `reputation_updated_at`
+    pub rewards: u64,           // Task rewards earned from accepted work (in $SCRAPE tokens)
     pub active: bool,           // Node status
     pub token_account: Pubkey,  // Node operator's $SCRAPE token account
-    pub last_bonus_claim: u64,  // Last reputation score at which bonus was claimed
+    pub last_bonus_claim: i64,  // Unix timestamp of the last bonus claim
+    pub strikes: u32,           // Assignments reclaimed after the node stalled
+    pub stake_account: Pubkey,  // Token account holding the node's staked $SCRAPE
+    pub stake: u64,             // $SCRAPE currently staked (slashable)
+    pub unstake_amount: u64,    // $SCRAPE requested for withdrawal, still slashable until withdrawn
+    pub unstake_requested_at: i64, // Unix timestamp of the pending unstake request
+    pub reported_bandwidth: u64, // Self-reported bandwidth telemetry (MB), never used for rewards
+    pub reputation_updated_at: i64, // Unix timestamp the reputation was last recomputed
+    pub tasks_accepted: u32,    // Results accepted (directly, automatically, or via dispute)
+    pub tasks_rejected: u32,    // Results rejected by task owners
+    pub disputes_lost: u32,     // Disputes settled against the node
+    pub session_key: Option<Pubkey>, // Delegated key allowed to act for the owner (e.g. complete tasks)
+    pub session_expires_at: i64, // Unix timestamp after which `session_key` is no longer accepted
+    pub registry_page: Option<u32>, // NodeRegistry page listing the node (None while deregistered)
+    pub active_tasks: u32,      // Tasks assigned to the node and not yet settled
+    pub capabilities: NodeCapabilities, // Published capability profile matched against task requirements
+    pub connection_commitment: [u8; 32], // Salted hash of the node's ConnectionInfo (zero until published)
+    pub bandwidth_window_start: i64, // Start of the current hourly bandwidth window
+    pub bandwidth_window_used: u64, // Bandwidth used in the current window (MB)
+    pub bonus_rewards: u64,     // Bonus emissions claimed (in $SCRAPE tokens)
 }
 
 impl ProviderNode {
     pub const PREFIX: &'static str = "PROVIDER_NODE";
+    pub const STAKE_PREFIX: &'static str = "NODE_STAKE";
+    pub const BANDWIDTH_WINDOW: i64 = 3600; // `bandwidth_limit` is per hour
 
     pub const SIZE: usize = 8 + // Discriminator
         std::mem::size_of::<u8>() + // bump
         std::mem::size_of::<Pubkey>() + // owner
-        4 * std::mem::size_of::<u8>() + // ipv4
-        4 * std::mem::size_of::<u16>() + // proxy_port
-        4 * std::mem::size_of::<u16>() + // client_port
+        4 * std::mem::size_of::<u8>() + // legacy_ipv4
+        4 * std::mem::size_of::<u16>() + // legacy_proxy_port
+        4 * std::mem::size_of::<u16>() + // legacy_client_port
         std::mem::size_of::<u64>() + // bandwidth_limit
         std::mem::size_of::<u64>() + // bandwidth_used
         std::mem::size_of::<u64>() + // reputation
         std::mem::size_of::<u64>() + // rewards
         std::mem::size_of::<bool>() + // active
         std::mem::size_of::<Pubkey>() + // token_account
-        std::mem::size_of::<u64>() + // last_bonus_claim
+        std::mem::size_of::<i64>() + // last_bonus_claim
+        std::mem::size_of::<u32>() + // strikes
+        std::mem::size_of::<Pubkey>() + // stake_account
+        std::mem::size_of::<u64>() + // stake
+        std::mem::size_of::<u64>() + // unstake_amount
+        std::mem::size_of::<i64>() + // unstake_requested_at
+        std::mem::size_of::<u64>() + // reported_bandwidth
+        std::mem::size_of::<i64>() + // reputation_updated_at
+        std::mem::size_of::<u32>() + // tasks_accepted
+        std::mem::size_of::<u32>() + // tasks_rejected
+        std::mem::size_of::<u32>() + // disputes_lost
+        (1 + 32) + // session_key (Option<Pubkey>)
+        std::mem::size_of::<i64>() + // session_expires_at
+        (1 + 4) + // registry_page (Option<u32>)
+        std::mem::size_of::<u32>() + // active_tasks
+        NodeCapabilities::SIZE + // capabilities
+        32 + // connection_commitment
+        std::mem::size_of::<i64>() + // bandwidth_window_start
+        std::mem::size_of::<u64>() + // bandwidth_window_used
+        std::mem::size_of::<u64>() + // bonus_rewards
         64; // padding
-}
\ No newline at end of file
+
+    /// MB the node may still move in the window containing `now`.
+    pub fn bandwidth_headroom(&self, now: i64) -> u64 {
+        if now >= self.bandwidth_window_start.saturating_add(Self::BANDWIDTH_WINDOW) {
+            self.bandwidth_limit
+        } else {
+            self.bandwidth_limit.saturating_sub(self.bandwidth_window_used)
+        }
+    }
+
+    /// Adds `mb` to the lifetime total and to the current window, starting a new window if the
+    /// previous one has elapsed.
+    pub fn record_bandwidth(&mut self, mb: u64, now: i64) -> Result<()> {
+        if now >= self.bandwidth_window_start.saturating_add(Self::BANDWIDTH_WINDOW) {
+            self.bandwidth_window_start = now - now.rem_euclid(Self::BANDWIDTH_WINDOW);
+            self.bandwidth_window_used = 0;
+        }
+        self.bandwidth_window_used = self
+            .bandwidth_window_used
+            .checked_add(mb)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.bandwidth_used = self.bandwidth_used.checked_add(mb).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Whether `signer` may act for this node: the owner, or its session key until it expires.
+    pub fn is_operator(&self, signer: &Pubkey, now: i64) -> bool {
+        *signer == self.owner
+            || (self.session_key == Some(*signer) && now < self.session_expires_at)
+    }
+
+    /// Whether the node's profile meets `requirements`, it has a free concurrency slot and it has
+    /// published connection info an endpoint can be sent.
+    pub fn can_take(&self, requirements: &TaskRequirements) -> bool {
+        self.connection_commitment != [0; 32]
+            && requirements.is_met_by(&self.capabilities)
+            && self.active_tasks < self.capabilities.max_concurrent_tasks as u32
+    }
+
+    /// Whether the stake not yet requested for withdrawal covers `min_stake`. Nodes below it may not
+    /// take work.
+    pub fn has_min_stake(&self, min_stake: u64) -> bool {
+        self.stake.saturating_sub(self.unstake_amount) >= min_stake
+    }
+
+    /// Reputation as of `now`: the stored score decayed over the time since it was last recomputed.
+    /// Anything that compares or weighs reputation must read it through here.
+    pub fn current_reputation(&self, now: i64, half_life: i64) -> u64 {
+        decay(self.reputation, now.saturating_sub(self.reputation_updated_at), half_life)
+    }
+
+    /// Whether the node may be drawn for, or stay selected for, a task with `requirements`. A node
+    /// that left the registry is out of the draw even if it was weighed before deregistering.
+    pub fn is_selectable(&self, min_stake: u64, requirements: &TaskRequirements) -> bool {
+        self.registry_page.is_some()
+            && self.active
+            && self.has_min_stake(min_stake)
+            && self.can_take(requirements)
+    }
+
+    /// Draw weight for provider selection: current reputation, stake and remaining hourly bandwidth
+    /// headroom all scale it. Unselectable or saturated nodes get zero.
+    pub fn selection_weight(
+        &self,
+        min_stake: u64,
+        requirements: &TaskRequirements,
+        now: i64,
+        half_life: i64,
+    ) -> u128 {
+        let headroom = self.bandwidth_headroom(now);
+        if !self.is_selectable(min_stake, requirements) || headroom == 0 {
+            return 0;
+        }
+
+        let stake_units = self.stake / min_stake.max(1);
+        (self.current_reputation(now, half_life) as u128 + 1)
+            .saturating_mul(stake_units as u128)
+            .saturating_mul(headroom as u128)
+    }
+
+    /// Applies decay since the last update, then the event's weight. This is the only way reputation changes.
+    pub fn record_reputation_event(&mut self, weights: &ReputationWeights, event: ReputationEvent, now: i64) {
+        let score = self.current_reputation(now, weights.half_life);
+
+        self.reputation = match event {
+            ReputationEvent::TaskAccepted => {
+                self.tasks_accepted = self.tasks_accepted.saturating_add(1);
+                score.saturating_add(weights.task_accepted)
+            }
+            ReputationEvent::TaskRejected => {
+                self.tasks_rejected = self.tasks_rejected.saturating_add(1);
+                score.saturating_sub(weights.task_rejected)
+            }
+            ReputationEvent::AssignmentStalled => score.saturating_sub(weights.assignment_stalled),
+            ReputationEvent::DisputeLost => {
+                self.disputes_lost = self.disputes_lost.saturating_add(1);
+                score.saturating_sub(weights.dispute_lost)
+            }
+        };
+        self.reputation_updated_at = now;
+
+        emit!(ReputationChanged {
+            node: self.owner,
+            event,
+            reputation: self.reputation,
+            timestamp: now,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node_with_session(session_key: Option<Pubkey>, session_expires_at: i64) -> ProviderNode {
+        ProviderNode {
+            owner: Pubkey::new_unique(),
+            session_key,
+            session_expires_at,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn owner_is_operator() {
+        let node = node_with_session(None, 0);
+
+        assert!(node.is_operator(&node.owner, 1_000));
+    }
+
+    #[test]
+    fn third_party_is_not_operator() {
+        let node = node_with_session(Some(Pubkey::new_unique()), 2_000);
+
+        assert!(!node.is_operator(&Pubkey::new_unique(), 1_000));
+    }
+
+    #[test]
+    fn bandwidth_window_limits_usage_within_the_hour() {
+        let mut node = ProviderNode { bandwidth_limit: 100, ..Default::default() };
+
+        node.record_bandwidth(60, 7_200).unwrap();
+        assert_eq!(node.bandwidth_headroom(7_200 + 3_599), 40);
+
+        node.record_bandwidth(50, 7_300).unwrap();
+        assert_eq!(node.bandwidth_headroom(7_300), 0);
+        assert_eq!(node.bandwidth_used, 110);
+    }
+
+    #[test]
+    fn bandwidth_window_rolls_over() {
+        let mut node = ProviderNode { bandwidth_limit: 100, ..Default::default() };
+
+        node.record_bandwidth(100, 7_200).unwrap();
+        assert_eq!(node.bandwidth_headroom(10_800), 100);
+
+        node.record_bandwidth(30, 10_900).unwrap();
+        assert_eq!(node.bandwidth_window_start, 10_800);
+        assert_eq!(node.bandwidth_headroom(10_900), 70);
+    }
+
+    #[test]
+    fn pending_unstake_does_not_count_towards_min_stake() {
+        let node = ProviderNode { stake: 150, unstake_amount: 60, ..Default::default() };
+
+        assert!(node.has_min_stake(90));
+        assert!(!node.has_min_stake(100));
+    }
+
+    #[test]
+    fn deregistered_node_is_not_selectable() {
+        let requirements = TaskRequirements::default();
+        let mut node = ProviderNode {
+            active: true,
+            stake: 100,
+            registry_page: Some(0),
+            connection_commitment: [1; 32],
+            capabilities: NodeCapabilities { max_concurrent_tasks: 1, ..Default::default() },
+            ..Default::default()
+        };
+        assert!(node.is_selectable(100, &requirements));
+
+        node.registry_page = None;
+        assert!(!node.is_selectable(100, &requirements));
+    }
+
+    #[test]
+    fn reputation_is_decayed_when_read() {
+        let node = ProviderNode { reputation: 400, reputation_updated_at: 1_000, ..Default::default() };
+
+        assert_eq!(node.current_reputation(1_000, 100), 400);
+        assert_eq!(node.current_reputation(1_200, 100), 100);
+    }
+
+    #[test]
+    fn session_key_is_operator_until_expiry() {
+        let session = Pubkey::new_unique();
+        let node = node_with_session(Some(session), 2_000);
+
+        assert!(node.is_operator(&session, 1_999));
+        assert!(!node.is_operator(&session, 2_000));
+    }
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Externally published randomness (e.g. a VRF oracle, or a local mock) used instead of slot hashes
+/// when `ConfigParams::randomness_feed` points at it.
+#[account]
+#[derive(Default, Debug)]
+pub struct RandomnessFeed {
+    pub bump: u8,               // Bump seed for PDA
+    pub authority: Pubkey,      // Oracle allowed to publish values
+    pub value: [u8; 32],        // Latest published random value
+    pub slot: u64,              // Slot the value was published at
+    pub round: u64,             // Number of values published so far
+}
+
+impl RandomnessFeed {
+    pub const PREFIX: &'static str = "RANDOMNESS_FEED";
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<Pubkey>() + // authority
+        32 + // value
+        std::mem::size_of::<u64>() + // slot
+        std::mem::size_of::<u64>() + // round
+        56; // padding
+}
+
+/// Slots between committing to a slot hash and the committed slot, see `select_provider`.
+pub const SEED_DELAY_SLOTS: u64 = 2;
+
+/// Finds the hash of the first slot at or after `slot` in the SlotHashes sysvar account data,
+/// skipping slots that produced no block. Returns `None` while no such slot has landed, or once it
+/// may have aged out of the sysvar.
+pub fn slot_hash_from(slot_hashes_data: &[u8], slot: u64) -> Option<(u64, [u8; 32])> {
+    // Layout: u64 entry count, then (u64 slot, [u8; 32] hash) entries, newest first
+    let entries = u64::from_le_bytes(slot_hashes_data.get(..8)?.try_into().ok()?);
+    let mut found = None;
+    for entry in slot_hashes_data.get(8..)?.chunks_exact(40).take(entries as usize) {
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().ok()?);
+        if entry_slot < slot {
+            return found;
+        }
+        found = Some((entry_slot, entry[8..].try_into().ok()?));
+    }
+    // Every retained slot is at or after `slot`: an earlier match may have been evicted
+    found.filter(|(entry_slot, _)| *entry_slot == slot)
+}
+
+/// Derives the selection seed for `task` from a randomness source and the current slot.
+pub fn selection_seed(randomness: &[u8; 32], task: &Pubkey, slot: u64) -> [u8; 32] {
+    hashv(&[randomness, task.as_ref(), &slot.to_le_bytes()]).to_bytes()
+}
+
+/// Exponential race key for `node`: across all nodes, the lowest key wins with probability
+/// proportional to `weight`. The winner depends only on the set of nodes and their weights, not on
+/// the order they are visited in, and seeing a node twice does not change it.
+pub fn draw_key(seed: &[u8; 32], node: &Pubkey, weight: u128) -> f64 {
+    let hash = hashv(&[seed, node.as_ref()]).to_bytes();
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&hash[..8]);
+    // Uniform in (0, 1] from the top 53 bits, the precision of an f64 mantissa
+    let uniform = ((u64::from_le_bytes(value) >> 11) + 1) as f64 / (1u64 << 53) as f64;
+    -uniform.ln() / weight as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot_hashes(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+        for slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[*slot as u8; 32]);
+        }
+        data
+    }
+
+    #[test]
+    fn slot_hash_from_skips_missing_slots() {
+        let data = slot_hashes(&[14, 13, 11, 10]);
+
+        assert_eq!(slot_hash_from(&data, 11), Some((11, [11; 32])));
+        assert_eq!(slot_hash_from(&data, 12), Some((13, [13; 32])));
+        assert_eq!(slot_hash_from(&data, 15), None);
+    }
+
+    #[test]
+    fn draw_key_favours_heavier_nodes() {
+        let seed = [7; 32];
+        let node = Pubkey::new_unique();
+
+        assert!(draw_key(&seed, &node, 10) < draw_key(&seed, &node, 1));
+        assert!(draw_key(&seed, &node, 1) >= 0.0);
+    }
+
+    #[test]
+    fn draw_key_winner_is_order_independent() {
+        let seed = [3; 32];
+        let nodes: Vec<(Pubkey, u128)> = (1..=8).map(|weight| (Pubkey::new_unique(), weight)).collect();
+        let winner = |order: &mut dyn Iterator<Item = &(Pubkey, u128)>| {
+            order
+                .map(|(node, weight)| (draw_key(&seed, node, *weight), *node))
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+                .map(|(_, node)| node)
+        };
+
+        assert_eq!(winner(&mut nodes.iter()), winner(&mut nodes.iter().rev()));
+    }
+
+    #[test]
+    fn slot_hash_from_rejects_evicted_slots() {
+        let data = slot_hashes(&[14, 13, 11]);
+
+        assert_eq!(slot_hash_from(&data, 11), Some((11, [11; 32])));
+        assert_eq!(slot_hash_from(&data, 9), None);
+    }
+}
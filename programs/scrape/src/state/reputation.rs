@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ReputationEvent {
+    TaskAccepted,       // Result accepted by the owner, auto-accepted, or upheld in a dispute
+    TaskRejected,       // Result rejected by the owner
+    AssignmentStalled,  // Assignment reclaimed after timing out
+    DisputeLost,        // Dispute settled against the provider
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default)]
+pub struct ReputationWeights {
+    pub task_accepted: u64,      // Score gained per accepted task
+    pub task_rejected: u64,      // Score lost per rejected result
+    pub assignment_stalled: u64, // Score lost per reclaimed assignment
+    pub dispute_lost: u64,       // Score lost per lost dispute
+    pub half_life: i64,          // Seconds for an idle score to decay by half
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct ReputationConfig {
+    pub bump: u8,               // Bump seed for PDA
+    pub authority: Pubkey,      // Authority allowed to tune the weights
+    pub weights: ReputationWeights, // Per-event weights and decay
+}
+
+impl ReputationConfig {
+    pub const PREFIX: &'static str = "REPUTATION_CONFIG";
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<Pubkey>() + // authority
+        4 * std::mem::size_of::<u64>() + // weights (event weights)
+        std::mem::size_of::<i64>() + // weights (half_life)
+        64; // padding
+}
+
+/// Decays `score` by `elapsed` seconds: whole half-lives halve it, the remainder is interpolated linearly.
+pub fn decay(score: u64, elapsed: i64, half_life: i64) -> u64 {
+    if elapsed <= 0 || half_life <= 0 {
+        return score;
+    }
+
+    let halvings = elapsed / half_life;
+    if halvings >= 64 {
+        return 0;
+    }
+    let score = score >> halvings;
+
+    let remainder = (elapsed % half_life) as u128;
+    let reduction = (score as u128) * remainder / (2 * half_life as u128);
+    score - reduction as u64
+}
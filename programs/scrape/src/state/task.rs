@@ -1,18 +1,66 @@
 use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::capability::TaskRequirements;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default)]
 pub enum TaskStatus {
+    #[default]
     Pending,    // Task is created but not yet assigned
     Assigned,   // Task is assigned to a node
+    Submitted,  // Node submitted a result, awaiting the owner's review
+    Disputed,   // Owner contested the result, awaiting arbiter settlement
     Completed,  // Task is completed and dataset is available
+    Rejected,   // Owner rejected the result; the provider may dispute it until the review window closes
 }
 
-impl Default for TaskStatus {
-    fn default() -> Self {
-        TaskStatus::Pending
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum StorageKind {
+    #[default]
+    Ipfs,       // IPFS CID (v0 base58 or v1 base32)
+    Arweave,    // Arweave transaction id
+    Https,      // HTTPS URL
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AssignerRole {
+    TaskOwner,  // The client who created the task
+    Endpoint,   // Owner of the task's endpoint node
+    SelfClaim,  // The provider node claiming the task for itself
+}
+
+/// Progress of a paged provider draw, see `select_provider`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default)]
+pub struct SelectionState {
+    pub seed: [u8; 32],         // Seed fixed when the draw started
+    pub next_page: u32,         // Registry page to process next
+    pub best_key: f64,          // Lowest `draw_key` seen so far, held by `candidate`
+    pub candidate: Option<Pubkey>, // Node currently holding the draw
+    pub commit_slot: u64,       // Slot whose hash seeds the draw, 0 once the seed is fixed
+    pub registry_epoch: u64,    // NodeRegistry epoch the current pass over the pages started in
+    pub commit_round: u64,      // RandomnessFeed round that seeds the draw, 0 once the seed is fixed
+}
+
+impl SelectionState {
+    /// A new pass over the registry pages that keeps the revealed seed.
+    pub fn restarted(&self, registry_epoch: u64) -> Self {
+        Self {
+            seed: self.seed,
+            registry_epoch,
+            ..Default::default()
+        }
     }
 }
 
+/// Description of a completed dataset, submitted by the provider with `complete_task`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default)]
+pub struct DatasetManifest {
+    pub size_bytes: u64,        // Dataset size in bytes
+    pub record_count: u64,      // Number of records in the dataset
+    pub content_hash: [u8; 32], // SHA-256 of the dataset contents
+    pub storage: StorageKind,   // Where `uri` points
+    pub uri: String,            // CID, Arweave tx id or HTTPS URL
+}
+
 #[account]
 #[derive(Default, Debug)]
 pub struct Task {
@@ -24,16 +72,38 @@ pub struct Task {
     pub filter: String,         // Semantic filter (e.g., "Positive Sentiment")
     pub label: String,          // Labeling (e.g., "Sentiment: Positive/Negative")
     pub format: String,         // Output format (e.g., JSONL)
-    pub reward: u64,            // Reward in $SCRAPE tokens
+    pub reward: u64,            // Reward in base units of `payment_mint`
     pub status: TaskStatus,     // Task status (now an enum)
     pub node_assigned: Option<Pubkey>, // Assigned node (if any)
-    pub ipfs_hash: Option<String>, // IPFS hash of scraped data (after completion)
-    pub dataset_size: u64,      // Size of the dataset in MB, updated after completion
+    pub dataset_uri: Option<String>, // Storage locator of scraped data (after completion)
+    pub dataset_size: u64,      // Size of the dataset in bytes, updated after completion
+    pub escrow: u64,            // Reward still locked in the vault for this task, net of transfer fees
+    pub deadline: i64,          // Unix timestamp after which an unassigned task can be refunded
+    pub created_at: i64,        // Unix timestamp of task creation
+    pub assigned_at: i64,       // Unix timestamp of the latest assignment (0 while unassigned)
+    pub submitted_at: i64,      // Unix timestamp the result was submitted (opens the review window)
+    pub record_count: u64,      // Number of records in the dataset
+    pub content_hash: [u8; 32], // SHA-256 of the dataset contents
+    pub storage: StorageKind,   // Storage backend `dataset_uri` refers to
+    pub allow_self_claim: bool, // Whether provider nodes may assign the task to themselves
+    pub assigned_by: Option<Pubkey>, // Signer of the latest assignment
+    pub selected_node: Option<Pubkey>, // Provider drawn by `select_provider`, the only one an endpoint may assign
+    pub selection: Option<SelectionState>, // Draw in progress across registry pages
+    pub requirements: TaskRequirements, // Node capabilities enforced on assignment
+    pub sealed_connection: Vec<u8>, // Assigned node's ConnectionInfo sealed to the endpoint's key (empty when not shared)
+    pub bandwidth_paid: u64,    // Bandwidth (MB) the reward pays for; caps the dataset size
+    pub payment_mint: Pubkey,   // Mint the reward is escrowed in
+    pub rejected_at: i64,       // Unix timestamp the owner rejected the result (opens the provider's dispute window)
 }
 
 impl Task {
     pub const PREFIX: &'static str = "TASK";
 
+    // x25519 sealed box: 32-byte ephemeral key + 16-byte tag + ConnectionInfo
+    pub const MAX_SEALED_CONNECTION_LEN: usize = 320;
+    pub const MAX_URI_LEN: usize = 256;
+    pub const BYTES_PER_MB: u64 = 1_000_000;
+
     pub const SIZE: usize = 8 + // Discriminator
         std::mem::size_of::<u8>() + // bump
         std::mem::size_of::<u64>() + // id
@@ -44,7 +114,197 @@ impl Task {
         std::mem::size_of::<u64>() + // reward
         1 + // status (TaskStatus enum, serialized as a u8 variant)
         (1 + 32) + // node_assigned (Option<Pubkey>)
-        (1 + 256) + // ipfs_hash (Option<String>, assuming max 256 chars)
+        (1 + 4 + Task::MAX_URI_LEN) + // dataset_uri (Option<String>)
         std::mem::size_of::<u64>() + // dataset_size
-        64; // padding
-}
\ No newline at end of file
+        std::mem::size_of::<u64>() + // escrow
+        std::mem::size_of::<i64>() + // deadline
+        std::mem::size_of::<i64>() + // created_at
+        std::mem::size_of::<i64>() + // assigned_at
+        std::mem::size_of::<i64>() + // submitted_at
+        std::mem::size_of::<u64>() + // record_count
+        32 + // content_hash
+        1 + // storage (StorageKind enum)
+        std::mem::size_of::<bool>() + // allow_self_claim
+        (1 + 32) + // assigned_by (Option<Pubkey>)
+        (1 + 32) + // selected_node (Option<Pubkey>)
+        (1 + 32 + 4 + 8 + 1 + 32 + 8 + 8 + 8) + // selection (Option<SelectionState>)
+        TaskRequirements::SIZE + // requirements
+        4 + Self::MAX_SEALED_CONNECTION_LEN + // sealed_connection
+        std::mem::size_of::<u64>() + // bandwidth_paid
+        std::mem::size_of::<Pubkey>() + // payment_mint
+        std::mem::size_of::<i64>() + // rejected_at
+        56; // padding
+
+    /// Resolves in which capacity `signer` may assign this task to the node owned by `node_owner`.
+    /// `endpoint_owner` is the owner of the task's endpoint node. Anyone else gets `None`.
+    pub fn assigner_role(
+        &self,
+        signer: &Pubkey,
+        endpoint_owner: &Pubkey,
+        node_owner: &Pubkey,
+    ) -> Option<AssignerRole> {
+        if *signer == self.owner {
+            Some(AssignerRole::TaskOwner)
+        } else if *signer == *endpoint_owner {
+            Some(AssignerRole::Endpoint)
+        } else if self.allow_self_claim && *signer == *node_owner {
+            Some(AssignerRole::SelfClaim)
+        } else {
+            None
+        }
+    }
+
+    /// Dataset size in whole MB, rounded up so small datasets are never free of bandwidth accounting.
+    pub fn dataset_size_mb(&self) -> u64 {
+        self.dataset_size.div_ceil(Self::BYTES_PER_MB)
+    }
+
+    /// MB of accepted work the task credits to its node's bonus share. The dataset is capped by the
+    /// bandwidth the escrow paid for, and a node working its owner's own task earns nothing, since
+    /// that escrow comes back to the same party.
+    pub fn bonus_contribution(&self) -> u64 {
+        if self.node_assigned == Some(self.owner) {
+            0
+        } else {
+            self.dataset_size_mb()
+        }
+    }
+
+    /// `amount` when the task pays in `scrape_mint`, else 0. Ledgers denominated in $SCRAPE
+    /// (client spend, node rewards) do not mix in other payment mints.
+    pub fn in_scrape(&self, scrape_mint: &Pubkey, amount: u64) -> u64 {
+        if self.payment_mint == *scrape_mint {
+            amount
+        } else {
+            0
+        }
+    }
+}
+
+impl DatasetManifest {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.size_bytes > 0, ErrorCode::InvalidDatasetSize);
+        require!(self.record_count > 0, ErrorCode::InvalidRecordCount);
+        require!(self.content_hash != [0; 32], ErrorCode::InvalidContentHash);
+        require!(
+            !self.uri.is_empty() && self.uri.len() <= Task::MAX_URI_LEN,
+            ErrorCode::InvalidStorageUri
+        );
+
+        let valid_uri = match self.storage {
+            StorageKind::Ipfs => is_ipfs_cid(&self.uri),
+            StorageKind::Arweave => is_arweave_tx(&self.uri),
+            StorageKind::Https => is_https_url(&self.uri),
+        };
+        require!(valid_uri, ErrorCode::InvalidStorageUri);
+        Ok(())
+    }
+}
+
+fn is_ipfs_cid(uri: &str) -> bool {
+    const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    if uri.starts_with("Qm") {
+        // CIDv0: base58btc multihash, always 46 chars
+        uri.len() == 46 && uri.chars().all(|c| BASE58.contains(c))
+    } else if let Some(body) = uri.strip_prefix('b') {
+        // CIDv1: multibase prefix 'b' followed by lowercase base32
+        uri.len() >= 59 && body.chars().all(|c| c.is_ascii_lowercase() || ('2'..='7').contains(&c))
+    } else {
+        false
+    }
+}
+
+fn is_arweave_tx(uri: &str) -> bool {
+    // 32-byte id, base64url without padding
+    uri.len() == 43 && uri.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn is_https_url(uri: &str) -> bool {
+    match uri.strip_prefix("https://") {
+        Some(rest) => !rest.is_empty() && uri.chars().all(|c| c.is_ascii_graphic()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Parties {
+        owner: Pubkey,
+        endpoint_owner: Pubkey,
+        node_owner: Pubkey,
+        stranger: Pubkey,
+    }
+
+    fn setup(allow_self_claim: bool) -> (Task, Parties) {
+        let parties = Parties {
+            owner: Pubkey::new_unique(),
+            endpoint_owner: Pubkey::new_unique(),
+            node_owner: Pubkey::new_unique(),
+            stranger: Pubkey::new_unique(),
+        };
+        let task = Task {
+            owner: parties.owner,
+            allow_self_claim,
+            ..Default::default()
+        };
+        (task, parties)
+    }
+
+    #[test]
+    fn task_owner_and_endpoint_owner_may_assign() {
+        let (task, p) = setup(false);
+
+        assert_eq!(
+            task.assigner_role(&p.owner, &p.endpoint_owner, &p.node_owner),
+            Some(AssignerRole::TaskOwner)
+        );
+        assert_eq!(
+            task.assigner_role(&p.endpoint_owner, &p.endpoint_owner, &p.node_owner),
+            Some(AssignerRole::Endpoint)
+        );
+    }
+
+    #[test]
+    fn unrelated_signer_cannot_assign() {
+        for allow_self_claim in [false, true] {
+            let (task, p) = setup(allow_self_claim);
+
+            assert_eq!(task.assigner_role(&p.stranger, &p.endpoint_owner, &p.node_owner), None);
+        }
+    }
+
+    #[test]
+    fn stranger_cannot_route_task_to_own_node() {
+        // A hijacker passing their own node is still neither owner nor endpoint
+        let (task, p) = setup(false);
+
+        assert_eq!(task.assigner_role(&p.stranger, &p.endpoint_owner, &p.stranger), None);
+    }
+
+    #[test]
+    fn own_task_earns_no_bonus_contribution() {
+        let (mut task, p) = setup(true);
+        task.dataset_size = 3 * Task::BYTES_PER_MB;
+
+        task.node_assigned = Some(p.node_owner);
+        assert_eq!(task.bonus_contribution(), 3);
+
+        task.node_assigned = Some(p.owner);
+        assert_eq!(task.bonus_contribution(), 0);
+    }
+
+    #[test]
+    fn node_may_self_claim_only_when_allowed() {
+        let (task, p) = setup(false);
+        assert_eq!(task.assigner_role(&p.node_owner, &p.endpoint_owner, &p.node_owner), None);
+
+        let (task, p) = setup(true);
+        assert_eq!(
+            task.assigner_role(&p.node_owner, &p.endpoint_owner, &p.node_owner),
+            Some(AssignerRole::SelfClaim)
+        );
+    }
+}
@@ -1,14 +1,28 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::error::ErrorCode;
 
 #[account]
 #[derive(Default, Debug)]
 pub struct TokenVault {
     pub bump: u8,               // Bump seed for PDA
-    pub owner: Pubkey,          // Program owner or authority (for future withdrawal)
-    pub token_account: Pubkey,  // The token account holding $SCRAPE tokens
-    pub total_rewards_distributed: u64, // Total $SCRAPE rewards distributed
-    pub bandwidth_paid: u64,    // Total bandwidth paid for (in MB, aggregated across tasks)
-    pub bandwidth_used: u64,    // Total bandwidth used (in MB, aggregated across tasks)
+    pub owner: Pubkey,          // Treasury authority: a single key or the TreasuryMultisig PDA
+    pub token_account: Pubkey,  // The token account holding the vault's tokens
+    pub total_rewards_distributed: u64, // Total rewards and bonuses paid out
+    pub bandwidth_paid_mb: u64, // Bandwidth paid for by open and finished tasks (MB)
+    pub bandwidth_used_mb: u64, // Bandwidth used by submitted datasets (MB)
+    pub total_escrowed: u64,    // Tokens currently locked in escrow for open tasks
+    pub total_slashed: u64,     // Tokens slashed from provider stakes into the vault
+    pub total_fees_collected: u64, // Tokens collected from dataset download payments
+    pub bonus_pool: u64,        // Tokens funded into bonus epochs and not yet claimed
+    pub treasury: u64,          // Fees and slashed stake owned by the protocol
+    pub total_escrow_deposited: u64, // Tokens deposited into escrow by task creation
+    pub bytes_delivered: u64,   // Dataset bytes submitted and not rejected
+    pub paused: bool,           // Circuit breaker tripped by a failed reconciliation
+    pub last_reconciled_slot: u64, // Slot of the last reconcile_vault
+    pub mint: Pubkey,           // Mint held by this vault; one vault exists per mint
+    pub approved: bool,         // Whether governance accepts the mint for new task rewards
+    pub rate_per_mb: u64,       // Task reward (in base units of `mint`) per MB of bandwidth it pays for
 }
 
 impl TokenVault {
@@ -19,7 +33,154 @@ impl TokenVault {
         std::mem::size_of::<Pubkey>() + // owner
         std::mem::size_of::<Pubkey>() + // token_account
         std::mem::size_of::<u64>() + // total_rewards_distributed
-        std::mem::size_of::<u64>() + // bandwidth_paid
-        std::mem::size_of::<u64>() + // bandwidth_used
-        7; // padding to align to 8-byte boundary (total size = 104 bytes)
-}
\ No newline at end of file
+        std::mem::size_of::<u64>() + // bandwidth_paid_mb
+        std::mem::size_of::<u64>() + // bandwidth_used_mb
+        std::mem::size_of::<u64>() + // total_escrowed
+        std::mem::size_of::<u64>() + // total_slashed
+        std::mem::size_of::<u64>() + // total_fees_collected
+        std::mem::size_of::<u64>() + // bonus_pool
+        std::mem::size_of::<u64>() + // treasury
+        std::mem::size_of::<u64>() + // total_escrow_deposited
+        std::mem::size_of::<u64>() + // bytes_delivered
+        std::mem::size_of::<bool>() + // paused
+        std::mem::size_of::<u64>() + // last_reconciled_slot
+        std::mem::size_of::<Pubkey>() + // mint
+        std::mem::size_of::<bool>() + // approved
+        std::mem::size_of::<u64>() + // rate_per_mb
+        7; // padding to align to 8-byte boundary
+
+    /// Tokens the vault owes across its pools; the token account must hold at least this much.
+    pub fn liabilities(&self) -> Result<u64> {
+        self.total_escrowed
+            .checked_add(self.bonus_pool)
+            .and_then(|amount| amount.checked_add(self.treasury))
+            .ok_or(ErrorCode::MathOverflow.into())
+    }
+
+    /// Whether the recorded pools are backed by `balance` and the bandwidth counters are consistent.
+    pub fn is_solvent(&self, balance: u64) -> Result<bool> {
+        Ok(balance >= self.liabilities()? && self.bandwidth_used_mb <= self.bandwidth_paid_mb)
+    }
+
+    // The vault's token account is shared by three separately accounted pools: escrow owed to
+    // clients and providers, bonus emissions owed to nodes, and the protocol treasury. A payout may
+    // only draw on its own pool.
+
+    pub fn lock_escrow(&mut self, amount: u64) -> Result<()> {
+        self.total_escrowed = self.total_escrowed.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn release_escrow(&mut self, amount: u64) -> Result<()> {
+        self.total_escrowed = self
+            .total_escrowed
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientEscrowBalance)?;
+        Ok(())
+    }
+
+    pub fn fund_bonus(&mut self, amount: u64) -> Result<()> {
+        self.bonus_pool = self.bonus_pool.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn pay_bonus(&mut self, amount: u64) -> Result<()> {
+        self.bonus_pool = self
+            .bonus_pool
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientBonusBalance)?;
+        self.total_rewards_distributed = self
+            .total_rewards_distributed
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn credit_treasury(&mut self, amount: u64) -> Result<()> {
+        self.treasury = self.treasury.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn debit_treasury(&mut self, amount: u64) -> Result<()> {
+        self.treasury = self
+            .treasury
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientTreasuryBalance)?;
+        Ok(())
+    }
+}
+
+/// TokenVault as stored under the single `[TOKEN_VAULT]` seed before vaults were kept per mint.
+/// Vaults created before reconciliation only hold the fields up to `bandwidth_used_mb`; the pool
+/// counters then read as zero.
+#[derive(AnchorDeserialize, Default, Debug)]
+pub struct LegacyTokenVault {
+    pub bump: u8,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub total_rewards_distributed: u64,
+    pub bandwidth_paid_mb: u64,
+    pub bandwidth_used_mb: u64,
+}
+
+/// Pool counters the legacy vault gained with reconciliation.
+#[derive(AnchorDeserialize, Default, Debug)]
+pub struct LegacyVaultPools {
+    pub total_escrowed: u64,
+    pub total_slashed: u64,
+    pub total_fees_collected: u64,
+    pub bonus_pool: u64,
+    pub treasury: u64,
+    pub total_escrow_deposited: u64,
+    pub bytes_delivered: u64,
+}
+
+impl LegacyTokenVault {
+    /// Parses legacy vault account data, discriminator included. Returns `None` for anything else,
+    /// including a vault already in the current layout.
+    pub fn parse(data: &[u8]) -> Option<(Self, LegacyVaultPools)> {
+        if data.len() == TokenVault::SIZE {
+            return None;
+        }
+        let mut data = data.strip_prefix(TokenVault::DISCRIMINATOR)?;
+        let vault = Self::deserialize(&mut data).ok()?;
+        let pools = LegacyVaultPools::deserialize(&mut data).unwrap_or_default();
+        Some((vault, pools))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_data(pools: Option<u64>, padding: usize) -> Vec<u8> {
+        let mut data = TokenVault::DISCRIMINATOR.to_vec();
+        data.push(254);
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&[7u64.to_le_bytes(), 8u64.to_le_bytes(), 9u64.to_le_bytes()].concat());
+        if let Some(value) = pools {
+            for _ in 0..7 {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        data.resize(data.len() + padding, 0);
+        data
+    }
+
+    #[test]
+    fn legacy_vault_layouts_are_parsed() {
+        let (vault, pools) = LegacyTokenVault::parse(&legacy_data(None, 7)).unwrap();
+        assert_eq!(vault.bump, 254);
+        assert_eq!(vault.bandwidth_used_mb, 9);
+        assert_eq!(pools.bonus_pool, 0);
+
+        let (vault, pools) = LegacyTokenVault::parse(&legacy_data(Some(5), 8)).unwrap();
+        assert_eq!(vault.total_rewards_distributed, 7);
+        assert_eq!(pools.total_escrowed, 5);
+        assert_eq!(pools.treasury, 5);
+
+        assert!(LegacyTokenVault::parse(&[0; TokenVault::SIZE]).is_none());
+        assert!(LegacyTokenVault::parse(&[0; 8]).is_none());
+    }
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub enum TreasuryAction {
+    Withdraw { amount: u64, destination: Pubkey }, // Pays treasury $SCRAPE to a token account
+    SetSigners { signers: Vec<Pubkey>, threshold: u8 }, // Replaces the multisig's signer set
+    SetOwner { mint: Pubkey, owner: Pubkey },      // Hands the vault of `mint` to another owner
+}
+
+impl Default for TreasuryAction {
+    fn default() -> Self {
+        TreasuryAction::Withdraw { amount: 0, destination: Pubkey::default() }
+    }
+}
+
+impl TreasuryAction {
+    pub const MAX_SIZE: usize = 1 + // variant
+        4 + TreasuryMultisig::MAX_SIGNERS * std::mem::size_of::<Pubkey>() + 1; // largest variant: SetSigners
+
+    pub fn is_valid(&self) -> bool {
+        match self {
+            TreasuryAction::Withdraw { amount, .. } => *amount > 0,
+            TreasuryAction::SetSigners { signers, threshold } => {
+                TreasuryMultisig::is_valid_signer_set(signers, *threshold)
+            }
+            TreasuryAction::SetOwner { owner, .. } => *owner != Pubkey::default(),
+        }
+    }
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct TreasuryMultisig {
+    pub bump: u8,               // Bump seed for PDA
+    pub signers: Vec<Pubkey>,   // Keys allowed to propose and approve treasury actions
+    pub threshold: u8,          // Approvals required to execute a proposal
+    pub signer_set_version: u32, // Bumped whenever the signer set changes; invalidates open proposals
+    pub proposal_count: u64,    // Counter used to derive proposal PDAs
+}
+
+impl TreasuryMultisig {
+    pub const PREFIX: &'static str = "TREASURY_MULTISIG";
+
+    pub const MAX_SIGNERS: usize = 16; // Approvals are tracked as a u16 bitmap
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        4 + Self::MAX_SIGNERS * std::mem::size_of::<Pubkey>() + // signers (Vec<Pubkey>)
+        std::mem::size_of::<u8>() + // threshold
+        std::mem::size_of::<u32>() + // signer_set_version
+        std::mem::size_of::<u64>() + // proposal_count
+        32; // padding
+
+    pub fn is_valid_signer_set(signers: &[Pubkey], threshold: u8) -> bool {
+        threshold > 0
+            && threshold as usize <= signers.len()
+            && signers.len() <= Self::MAX_SIGNERS
+            && signers.iter().enumerate().all(|(i, signer)| !signers[..i].contains(signer))
+    }
+
+    pub fn signer_index(&self, key: &Pubkey) -> Option<usize> {
+        self.signers.iter().position(|signer| signer == key)
+    }
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct TreasuryProposal {
+    pub bump: u8,               // Bump seed for PDA
+    pub id: u64,                // Index in the multisig's proposal counter
+    pub proposer: Pubkey,       // Signer who created the proposal
+    pub action: TreasuryAction, // Action performed on execution
+    pub approvals: u16,         // Bitmap of approving signers, indexed like `TreasuryMultisig.signers`
+    pub signer_set_version: u32, // Signer set the approvals refer to
+    pub executed: bool,         // Whether the action has been performed
+    pub created_at: i64,        // Unix timestamp of the proposal
+}
+
+impl TreasuryProposal {
+    pub const PREFIX: &'static str = "TREASURY_PROPOSAL";
+
+    pub const SIZE: usize = 8 + // Discriminator
+        std::mem::size_of::<u8>() + // bump
+        std::mem::size_of::<u64>() + // id
+        std::mem::size_of::<Pubkey>() + // proposer
+        TreasuryAction::MAX_SIZE + // action
+        std::mem::size_of::<u16>() + // approvals
+        std::mem::size_of::<u32>() + // signer_set_version
+        std::mem::size_of::<bool>() + // executed
+        std::mem::size_of::<i64>() + // created_at
+        32; // padding
+
+    pub fn approval_count(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signer_set_requires_distinct_signers_and_reachable_threshold() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+
+        assert!(TreasuryMultisig::is_valid_signer_set(&[a, b], 2));
+        assert!(!TreasuryMultisig::is_valid_signer_set(&[a, b], 3));
+        assert!(!TreasuryMultisig::is_valid_signer_set(&[a, b], 0));
+        assert!(!TreasuryMultisig::is_valid_signer_set(&[a, a], 1));
+        assert!(!TreasuryMultisig::is_valid_signer_set(&[a; TreasuryMultisig::MAX_SIGNERS + 1], 1));
+    }
+
+    #[test]
+    fn action_size_fits_largest_signer_set() {
+        let action = TreasuryAction::SetSigners {
+            signers: vec![Pubkey::new_unique(); TreasuryMultisig::MAX_SIGNERS],
+            threshold: 1,
+        };
+
+        assert_eq!(action.try_to_vec().unwrap().len(), TreasuryAction::MAX_SIZE);
+    }
+}
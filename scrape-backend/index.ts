@@ -2,13 +2,13 @@ import express, { Express, Request, Response } from "express";
 import { createClient } from "@supabase/supabase-js";
 import cors from "cors";
 import fetch from "node-fetch";
+import { createHash } from "crypto";
 import { parse } from "node-html-parser";
 import {
   Connection,
   PublicKey,
   Transaction,
 } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
 import BN from "bn.js";
 import {
   findTaskPda,
@@ -16,6 +16,13 @@ import {
   findEndpointNodePda,
   findNodeRegistryPda,
   findTokenVaultPda,
+  findConfigPda,
+  findReputationConfigPda,
+  findRegistryPagePda,
+  decodeTaskSelection,
+  decodeTaskPaymentMint,
+  decodeRegistryPageNodes,
+  createSelectProviderInstruction,
   createAssignTaskByEndpointInstruction,
   createCompleteTaskInstruction,
   createUpdateNodeReportInstruction,
@@ -34,40 +41,32 @@ const supabase = createClient(supabaseUrl, supabaseKey);
 const connection = new Connection("https://api.devnet.solana.com", "confirmed");
 const PROGRAM_ID = new PublicKey("7pqme6UtiQshBaes6hQ2HkEwnwUph1JsEujZzKi9rmxU");
 
-// Function to fetch active provider nodes
-async function getActiveProviderNodes(): Promise<{ owner: PublicKey; pda: PublicKey }[]> {
+// Function to fetch the provider nodes listed on each registry page, in page order
+async function getRegistryPages(): Promise<{ index: number; pda: PublicKey; nodes: PublicKey[] }[]> {
   const [registryPda] = await findNodeRegistryPda();
   const registryAccount = await connection.getAccountInfo(registryPda);
   if (!registryAccount) {
     throw new Error("Node registry not found");
   }
 
-  const registryData = registryAccount.data;
-  let offset = 8 + 1; // Skip discriminator and bump
-  const nodeCount = registryData.readUInt32LE(offset);
-  offset += 4;
-  const nodeOwners: PublicKey[] = [];
-  for (let i = 0; i < nodeCount; i++) {
-    const owner = new PublicKey(registryData.slice(offset, offset + 32));
-    nodeOwners.push(owner);
-    offset += 32;
-  }
+  const pageCount = registryAccount.data.readUInt32LE(8 + 1); // Skip discriminator and bump
+  const pages: { index: number; pda: PublicKey; nodes: PublicKey[] }[] = [];
+  for (let index = 0; index < pageCount; index++) {
+    const [pagePda] = await findRegistryPagePda(index);
+    const pageAccount = await connection.getAccountInfo(pagePda);
+    if (!pageAccount) {
+      throw new Error(`Registry page ${index} not found`);
+    }
 
-  const activeNodes: { owner: PublicKey; pda: PublicKey }[] = [];
-  for (const owner of nodeOwners) {
-    const [nodePda] = await findProviderNodePda(owner);
-    const nodeAccount = await connection.getAccountInfo(nodePda);
-    if (nodeAccount) {
-      const data = nodeAccount.data;
-      let offset = 8 + 1 + 32 + 4 + 2 + 2 + 8 + 8 + 8 + 8; // Skip to active field
-      const active = data[offset] === 1;
-      if (active) {
-        activeNodes.push({ owner, pda: nodePda });
-      }
+    const nodes: PublicKey[] = [];
+    for (const owner of decodeRegistryPageNodes(pageAccount.data)) {
+      const [nodePda] = await findProviderNodePda(owner);
+      nodes.push(nodePda);
     }
+    pages.push({ index, pda: pagePda, nodes });
   }
 
-  return activeNodes;
+  return pages;
 }
 
 app.get("/", (req: Request, res: Response) => {
@@ -117,46 +116,83 @@ app.post(
       const clientPubkey = new PublicKey(owner);
       const [taskPda] = await findTaskPda(clientPubkey, new BN(taskIdNum));
 
-      // Fetch active provider nodes and select one
-      const activeNodes = await getActiveProviderNodes();
-      if (activeNodes.length === 0) {
-        console.log("No active provider nodes available");
-        res.status(400).json({ error: "No active provider nodes available" });
+      // Derive the EndpointNode and NodeRegistry PDAs
+      const [endpointNodePda] = await findEndpointNodePda(clientPubkey);
+      const [registryPda] = await findNodeRegistryPda();
+      const [configPda] = await findConfigPda();
+      const [reputationConfigPda] = await findReputationConfigPda();
+
+      // The provider is drawn on-chain by select_provider; read the result from the task
+      const taskAccount = await connection.getAccountInfo(taskPda);
+      if (!taskAccount) {
+        res.status(404).json({ error: `Task ${taskId} not found` });
         return;
       }
+      const selection = decodeTaskSelection(taskAccount.data);
+      const selectedOwner = selection.selectedNode;
+      if (!selectedOwner) {
+        const pages = await getRegistryPages();
+        if (pages.length === 0) {
+          console.log("No provider nodes registered");
+          res.status(400).json({ error: "No provider nodes registered" });
+          return;
+        }
 
-      // Simple selection: choose the first active node
-      const selectedNode = activeNodes[0];
-      const nodePubkey = selectedNode.owner;
-      const nodePda = selectedNode.pda;
+        // One select_provider transaction per remaining page; they must land in order. The first call
+        // only commits the draw to randomness that is not known yet, so it is sent alone. The last page has to name the
+        // candidate carried over from earlier pages, so it is only built once those have landed and
+        // the task is resubmitted
+        const { blockhash } = await connection.getLatestBlockhash();
+        const remainingPages = pages.slice(selection.nextPage);
+        const isLastPage = remainingPages.length === 1;
+        const candidateNode = isLastPage && selection.candidate ? (await findProviderNodePda(selection.candidate))[0] : null;
+        const pagesToSend = !selection.started
+          ? remainingPages.slice(0, 1)
+          : isLastPage
+            ? remainingPages
+            : remainingPages.slice(0, -1);
+        const serializedSelectProviderTxs = pagesToSend.map((page) => {
+          const selectProviderInstruction = createSelectProviderInstruction({
+            task: taskPda,
+            endpoint_node: endpointNodePda,
+            node_registry: registryPda,
+            registry_page: page.pda,
+            randomness_feed: null,
+            signer: clientPubkey,
+            config: configPda,
+            reputation_config: reputationConfigPda,
+            candidate_node: candidateNode,
+            provider_nodes: page.nodes,
+          });
+
+          const selectProviderTx = new Transaction().add(selectProviderInstruction);
+          selectProviderTx.recentBlockhash = blockhash;
+          selectProviderTx.feePayer = clientPubkey;
+          return selectProviderTx.serialize({ requireAllSignatures: false }).toString("base64");
+        });
+        console.log("Serialized selectProviderTxs (to be signed by clientPubkey):", serializedSelectProviderTxs);
 
-      console.log(`Selected provider node: ${nodePubkey.toString()}`);
+        // The client signs and sends the draw, then resubmits the task
+        res.status(202).json({
+          message: `Task ${taskId} has no provider yet. Sign and send selectProviderTxs in order, then resubmit the task.`,
+          selectProviderTxs: serializedSelectProviderTxs,
+          signerPubkey: clientPubkey.toString(),
+        });
+        return;
+      }
 
-      // Derive the EndpointNode PDA
-      const [endpointNodePda] = await findEndpointNodePda(clientPubkey);
+      const nodePubkey = selectedOwner;
+      const [nodePda] = await findProviderNodePda(nodePubkey);
+
+      console.log(`Selected provider node: ${nodePubkey.toString()}`);
 
       // Log the public keys to identify the problematic one
       console.log("Client/Owner Public Key:", clientPubkey.toString());
       console.log("Endpoint Node PDA:", endpointNodePda.toString());
       console.log("Selected Node Public Key:", nodePubkey.toString());
 
-      // Derive the NodeRegistry PDA
-      const [registryPda] = await findNodeRegistryPda();
-
-      // Derive TokenVault and associated token accounts
-      const [tokenVaultPda] = await findTokenVaultPda();
-
-      const vaultTokenAccount = await getAssociatedTokenAddress(
-        new PublicKey("6F2hasc11STQVPcZfX6E767wWV6TZXQRw74fAe11hCH3"),
-        tokenVaultPda,
-        true
-      );
-
-      const nodeTokenAccount = await getAssociatedTokenAddress(
-        new PublicKey("6F2hasc11STQVPcZfX6E767wWV6TZXQRw74fAe11hCH3"),
-        nodePda,
-        true
-      );
+      // Derive the TokenVault of the task's payment mint (rewards are paid out when the owner accepts the result)
+      const [tokenVaultPda] = await findTokenVaultPda(decodeTaskPaymentMint(taskAccount.data));
 
       // Create assign_task_by_endpoint instruction
       console.log("Creating assign_task_by_endpoint instruction...");
@@ -166,6 +202,7 @@ app.post(
         signer: clientPubkey, // The signer is the owner of the endpoint_node
         node_registry: registryPda,
         provider_node: nodePda,
+        config: configPda,
       });
 
       const assignTaskTx = new Transaction().add(assignTaskInstruction);
@@ -229,8 +266,9 @@ app.post(
       // Calculate bandwidth used and dataset size
       const bandwidthUsedBytes = Buffer.from(html).length;
       console.log("Bandwidth used (bytes):", bandwidthUsedBytes);
-      const datasetSizeBytes = Buffer.from(jsonlData).length;
-      const datasetSizeMB = Math.round(datasetSizeBytes / 1_000_000);
+      const datasetBytes = Buffer.from(jsonlData);
+      const datasetSizeBytes = datasetBytes.length;
+      const contentHash = createHash("sha256").update(datasetBytes).digest();
 
       // Create complete_task instruction
       console.log("Creating complete_task instruction...");
@@ -239,12 +277,15 @@ app.post(
           task: taskPda,
           node: nodePda,
           token_vault: tokenVaultPda,
-          vault_token_account: vaultTokenAccount,
-          node_token_account: nodeTokenAccount,
           signer: nodePubkey,
-          token_program: TOKEN_PROGRAM_ID,
         },
-        { ipfs_hash: urlData.publicUrl }
+        {
+          size_bytes: new BN(datasetSizeBytes),
+          record_count: new BN(scrapedData.length),
+          content_hash: contentHash,
+          storage: "https",
+          uri: urlData.publicUrl,
+        }
       );
 
       const completeTaskTx = new Transaction().add(completeTaskInstruction);
@@ -262,7 +303,6 @@ app.post(
         },
         {
           bandwidth_used: new BN(bandwidthUsedBytes),
-          reputation_increase: new BN(0),
         }
       );
 
@@ -276,7 +316,7 @@ app.post(
         message: `Task ${taskId} processed successfully`,
         taskDetails: { taskId, url, filter, label, format, selector },
         downloadUrl: urlData.publicUrl,
-        datasetSize: datasetSizeMB,
+        datasetSize: datasetSizeBytes,
         assignTaskTx: serializedAssignTaskTx,
         completeTaskTx: serializedCompleteTaskTx,
         updateNodeReportTx: serializedUpdateNodeReportTx,
@@ -1,5 +1,6 @@
-import { PublicKey, TransactionInstruction, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+import { PublicKey, TransactionInstruction, SystemProgram, SYSVAR_RENT_PUBKEY, SYSVAR_SLOT_HASHES_PUBKEY } from '@solana/web3.js';
 import BN from 'bn.js';
+import { createHash } from 'crypto';
 
 // Program ID
 export const PROGRAM_ID = new PublicKey('7pqme6UtiQshBaes6hQ2HkEwnwUph1JsEujZzKi9rmxU');
@@ -37,9 +38,9 @@ export async function findProviderNodePda(owner: PublicKey, programId: PublicKey
   );
 }
 
-export async function findTokenVaultPda(programId: PublicKey = PROGRAM_ID): Promise<[PublicKey, number]> {
+export async function findTokenVaultPda(mint: PublicKey, programId: PublicKey = PROGRAM_ID): Promise<[PublicKey, number]> {
   return PublicKey.findProgramAddress(
-    [Buffer.from("TOKEN_VAULT")],
+    [Buffer.from("TOKEN_VAULT"), mint.toBuffer()],
     programId
   );
 }
@@ -51,11 +52,70 @@ export async function findNodeRegistryPda(programId: PublicKey = PROGRAM_ID): Pr
   );
 }
 
+export async function findRegistryPagePda(index: number, programId: PublicKey = PROGRAM_ID): Promise<[PublicKey, number]> {
+  const indexBuffer = Buffer.alloc(4);
+  indexBuffer.writeUInt32LE(index, 0);
+  return PublicKey.findProgramAddress(
+    [Buffer.from("NODE_REGISTRY_PAGE"), indexBuffer],
+    programId
+  );
+}
+
+// Epoch number is unix timestamp / `bonus_epoch_length`
+export async function findBonusEpochPda(epoch: BN, programId: PublicKey = PROGRAM_ID): Promise<[PublicKey, number]> {
+  return PublicKey.findProgramAddress(
+    [Buffer.from("BONUS_EPOCH"), epoch.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+export async function findBonusClaimPda(node: PublicKey, epoch: BN, programId: PublicKey = PROGRAM_ID): Promise<[PublicKey, number]> {
+  return PublicKey.findProgramAddress(
+    [Buffer.from("BONUS_CLAIM"), node.toBuffer(), epoch.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+export async function findConfigPda(programId: PublicKey = PROGRAM_ID): Promise<[PublicKey, number]> {
+  return PublicKey.findProgramAddress(
+    [Buffer.from("CONFIG")],
+    programId
+  );
+}
+
+export async function findReputationConfigPda(programId: PublicKey = PROGRAM_ID): Promise<[PublicKey, number]> {
+  return PublicKey.findProgramAddress(
+    [Buffer.from("REPUTATION_CONFIG")],
+    programId
+  );
+}
+
+export async function findDatasetAccessPda(task: PublicKey, buyer: PublicKey, programId: PublicKey = PROGRAM_ID): Promise<[PublicKey, number]> {
+  return PublicKey.findProgramAddress(
+    [Buffer.from("DATASET_ACCESS"), task.toBuffer(), buyer.toBuffer()],
+    programId
+  );
+}
+
+export async function findDownloadReceiptPda(task: PublicKey, buyer: PublicKey, programId: PublicKey = PROGRAM_ID): Promise<[PublicKey, number]> {
+  return PublicKey.findProgramAddress(
+    [Buffer.from("DOWNLOAD_RECEIPT"), task.toBuffer(), buyer.toBuffer()],
+    programId
+  );
+}
+
 // Custom Types
 export interface Client {
   bump: number;
   owner: PublicKey;
   task_counter: BN;
+  free_allowance_used: BN;
+  allowance_period_start: BN;
+  total_spent: BN;
+  total_spent_lamports: BN;
+  bytes_downloaded: BN;
+  open_tasks: number;
+  completed_tasks: BN;
 }
 
 export interface EndpointNode {
@@ -65,22 +125,79 @@ export interface EndpointNode {
 
 export interface NodeRegistry {
   bump: number;
-  nodes: PublicKey[];
+  page_count: number;
+  node_count: number;
+  epoch: BN; // Bumped when compaction moves nodes between pages
+}
+
+export interface RegistryPage {
+  bump: number;
+  index: number;
+  count: number;
+  slots: PublicKey[]; // PublicKey.default marks a free slot
 }
 
+export type NodeAddress =
+  | { v4: number[] } // 4 octets
+  | { v6: number[] } // 16 octets
+  | { hostname: string }; // RFC 1123 hostname
+
 export interface ProviderNode {
   bump: number;
   owner: PublicKey;
-  ipv4: number[];
-  proxy_port: number;
-  client_port: number;
+  legacy_ipv4: number[]; // Former plaintext IP, zeroed once migrated
+  legacy_proxy_port: number;
+  legacy_client_port: number;
   bandwidth_limit: BN;
   bandwidth_used: BN;
   reputation: BN;
-  rewards: BN;
+  rewards: BN; // Task rewards from accepted work
   active: boolean;
   token_account: PublicKey;
-  last_bonus_claim: BN;
+  last_bonus_claim: BN; // Unix timestamp
+  connection_commitment: number[]; // Salted SHA-256 of the node's ConnectionInfo
+}
+
+// Kept off-chain; only its commitment is published and it is shared per task sealed to the endpoint
+export interface ConnectionInfo {
+  addresses: NodeAddress[];
+  proxy_port: number;
+  client_port: number;
+}
+
+// Borsh-serializes a Vec<NodeAddress>
+function serializeNodeAddresses(addresses: NodeAddress[]): Buffer {
+  const buffers: Buffer[] = [];
+  const lenBuffer = Buffer.alloc(4);
+  lenBuffer.writeUInt32LE(addresses.length, 0);
+  buffers.push(lenBuffer);
+
+  for (const address of addresses) {
+    if ("v4" in address) {
+      buffers.push(Buffer.from([0, ...address.v4]));
+    } else if ("v6" in address) {
+      buffers.push(Buffer.from([1, ...address.v6]));
+    } else {
+      const nameBytes = Buffer.from(address.hostname, "utf8");
+      const nameLenBuffer = Buffer.alloc(4);
+      nameLenBuffer.writeUInt32LE(nameBytes.length, 0);
+      buffers.push(Buffer.from([2]), nameLenBuffer, nameBytes);
+    }
+  }
+  return Buffer.concat(buffers);
+}
+
+// Commitment published with create_node/update_node: SHA-256 over the Borsh encoding followed by a secret salt
+export function computeConnectionCommitment(info: ConnectionInfo, salt: Buffer): Buffer {
+  if (salt.length !== 32) {
+    throw new Error("salt must be 32 bytes");
+  }
+  const ports = Buffer.alloc(4);
+  ports.writeUInt16LE(info.proxy_port, 0);
+  ports.writeUInt16LE(info.client_port, 2);
+  return createHash("sha256")
+    .update(Buffer.concat([serializeNodeAddresses(info.addresses), ports, salt]))
+    .digest();
 }
 
 export interface Task {
@@ -95,23 +212,65 @@ export interface Task {
   reward: BN;
   status: TaskStatus;
   node_assigned: PublicKey | null;
-  ipfs_hash: string | null;
-  dataset_size: BN;
+  dataset_uri: string | null; // Storage locator, interpreted according to `storage`
+  dataset_size: BN; // Bytes
+  escrow: BN;
+  deadline: BN; // Unix timestamp
+  created_at: BN;
+  assigned_at: BN;
+  submitted_at: BN;
+  record_count: BN;
+  content_hash: number[]; // SHA-256 of the dataset contents
+  storage: StorageKind;
+  allow_self_claim: boolean;
+  assigned_by: PublicKey | null;
+  selected_node: PublicKey | null;
+  selection: SelectionState | null;
+  requirements: TaskRequirements;
+  sealed_connection: Buffer; // ConnectionInfo sealed to the endpoint's key
+  bandwidth_paid: BN; // MB
+  payment_mint: PublicKey; // Mint the reward is escrowed in; selects the TokenVault
+  rejected_at: BN; // Unix timestamp of the owner's rejection, 0 otherwise
 }
 
 export type TaskStatus = {
   Pending: {};
   Assigned: {};
+  Submitted: {};
+  Disputed: {};
   Completed: {};
+  Rejected: {};
 };
 
+export interface SelectionState {
+  seed: number[];
+  next_page: number;
+  best_key: number; // Lowest draw key seen so far, held by `candidate`
+  candidate: PublicKey | null;
+  commit_slot: BN; // Slot whose hash seeds the draw, 0 once the seed is fixed
+  registry_epoch: BN; // NodeRegistry epoch of the current pass; compaction restarts the pass with the same seed
+  commit_round: BN; // RandomnessFeed round that seeds the draw, 0 once the seed is fixed
+}
+
 export interface TokenVault {
   bump: number;
   owner: PublicKey;
   token_account: PublicKey;
   total_rewards_distributed: BN;
-  bandwidth_paid: BN;
-  bandwidth_used: BN;
+  bandwidth_paid_mb: BN;
+  bandwidth_used_mb: BN;
+  total_escrowed: BN; // Escrow pool
+  total_slashed: BN;
+  total_fees_collected: BN;
+  bonus_pool: BN; // Unclaimed bonus emissions
+  treasury: BN; // Protocol-owned fees and slashed stake
+  total_escrow_deposited: BN;
+  bytes_delivered: BN;
+  paused: boolean; // Set by reconcile_vault when the pools are not backed by the balance
+  last_reconciled_slot: BN;
+  mint: PublicKey; // One vault per mint
+  approved: boolean; // Whether new tasks may pay in this mint
+  rate_per_mb: BN; // Reward per MB of bandwidth, in base units of `mint`
 }
 
 // Instruction Types and Functions
@@ -148,6 +307,7 @@ export interface AssignTaskByEndpointAccounts {
   signer: PublicKey;
   node_registry: PublicKey;
   provider_node: PublicKey;
+  config: PublicKey;
 }
 
 export function createAssignTaskByEndpointInstruction(
@@ -159,7 +319,8 @@ export function createAssignTaskByEndpointInstruction(
     { pubkey: accounts.endpoint_node, isSigner: false, isWritable: false },
     { pubkey: accounts.signer, isSigner: true, isWritable: true },
     { pubkey: accounts.node_registry, isSigner: false, isWritable: false },
-    { pubkey: accounts.provider_node, isSigner: false, isWritable: false },
+    { pubkey: accounts.provider_node, isSigner: false, isWritable: true },
+    { pubkey: accounts.config, isSigner: false, isWritable: false },
   ];
 
   const discriminator = Buffer.from([82, 113, 249, 1, 61, 227, 106, 89]);
@@ -172,30 +333,163 @@ export function createAssignTaskByEndpointInstruction(
   });
 }
 
-export interface ClaimTaskRewardsAccounts {
+export interface SelectProviderAccounts {
+  task: PublicKey;
+  endpoint_node: PublicKey;
+  node_registry: PublicKey;
+  registry_page: PublicKey; // Page at the task's next selection index
+  randomness_feed: PublicKey | null; // Required when the config sets a randomness feed
+  signer: PublicKey;
+  config: PublicKey;
+  reputation_config: PublicKey; // Its half-life decays node reputation for the draw weights
+  candidate_node: PublicKey | null; // Last page only: ProviderNode PDA of a candidate drawn on an earlier page
+  provider_nodes: PublicKey[]; // ProviderNode PDAs listed on `registry_page`, in slot order
+}
+
+export function createSelectProviderInstruction(
+  accounts: SelectProviderAccounts,
+  programId: PublicKey = PROGRAM_ID
+): TransactionInstruction {
+  const keys = [
+    { pubkey: accounts.task, isSigner: false, isWritable: true },
+    { pubkey: accounts.endpoint_node, isSigner: false, isWritable: false },
+    { pubkey: accounts.node_registry, isSigner: false, isWritable: false },
+    { pubkey: accounts.registry_page, isSigner: false, isWritable: false },
+    { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false },
+    // Anchor treats the program ID as "not provided" for optional accounts
+    { pubkey: accounts.randomness_feed ?? programId, isSigner: false, isWritable: false },
+    { pubkey: accounts.signer, isSigner: true, isWritable: false },
+    { pubkey: accounts.config, isSigner: false, isWritable: false },
+    { pubkey: accounts.reputation_config, isSigner: false, isWritable: false },
+    { pubkey: accounts.candidate_node ?? programId, isSigner: false, isWritable: false },
+    ...accounts.provider_nodes.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })),
+  ];
+
+  const discriminator = Buffer.from([213, 13, 121, 135, 228, 93, 123, 174]);
+  const data = discriminator;
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data,
+  });
+}
+
+export interface TaskSelection {
+  selectedNode: PublicKey | null; // Provider drawn for the task, once the draw has completed
+  nextPage: number; // Registry page the draw continues from
+  candidate: PublicKey | null; // Owner currently holding the draw, if any
+  started: boolean; // Whether the draw has committed to its seed
+}
+
+// Offset of `Task.selected_node` in raw account data
+function selectedNodeOffset(data: Buffer): number {
+  let offset = 8 + 1 + 8 + 32 + 32; // discriminator, bump, id, owner, endpoint_node
+  for (let i = 0; i < 4; i++) {
+    offset += 4 + data.readUInt32LE(offset); // url, filter, label, format
+  }
+  offset += 8 + 1; // reward, status
+  offset += data[offset] === 1 ? 1 + 32 : 1; // node_assigned
+  offset += data[offset] === 1 ? 1 + 4 + data.readUInt32LE(offset + 1) : 1; // dataset_uri
+  offset += 8 * 8 + 32 + 1 + 1; // dataset_size .. record_count, content_hash, storage, allow_self_claim
+  offset += data[offset] === 1 ? 1 + 32 : 1; // assigned_by
+  return offset;
+}
+
+// Reads `Task.selected_node` and the draw progress from raw account data
+export function decodeTaskSelection(data: Buffer): TaskSelection {
+  let offset = selectedNodeOffset(data);
+
+  let selectedNode: PublicKey | null = null;
+  if (data[offset] === 1) {
+    selectedNode = new PublicKey(data.slice(offset + 1, offset + 33));
+    offset += 1 + 32;
+  } else {
+    offset += 1;
+  }
+
+  // selection: Option<{ seed: [u8; 32], next_page: u32, best_key: f64, candidate: Option<Pubkey>, commit_slot: u64, registry_epoch: u64, commit_round: u64 }>
+  if (data[offset] !== 1) {
+    return { selectedNode, nextPage: 0, candidate: null, started: false };
+  }
+  const nextPage = data.readUInt32LE(offset + 1 + 32);
+  offset += 1 + 32 + 4 + 8;
+  const candidate = data[offset] === 1 ? new PublicKey(data.slice(offset + 1, offset + 33)) : null;
+  return { selectedNode, nextPage, candidate, started: true };
+}
+
+// Reads `Task.payment_mint` from raw account data
+export function decodeTaskPaymentMint(data: Buffer): PublicKey {
+  let offset = selectedNodeOffset(data);
+  offset += data[offset] === 1 ? 1 + 32 : 1; // selected_node
+  if (data[offset] === 1) {
+    offset += 1 + 32 + 4 + 8; // selection: seed, next_page, best_key
+    offset += data[offset] === 1 ? 1 + 32 : 1; // selection.candidate
+    offset += 8 + 8 + 8; // selection.commit_slot, registry_epoch, commit_round
+  } else {
+    offset += 1;
+  }
+  offset += data[offset] === 1 ? 1 + 2 : 1; // requirements.country
+  offset += data[offset] === 1 ? 1 + 3 : 1; // requirements.region
+  offset += data[offset] === 1 ? 1 + 1 : 1; // requirements.asn_class
+  offset += 3; // requirements.ipv6, js_rendering, formats
+  offset += 4 + data.readUInt32LE(offset); // sealed_connection
+  offset += 8; // bandwidth_paid
+  return new PublicKey(data.slice(offset, offset + 32));
+}
+
+// Reads the occupied slots of a RegistryPage, in slot order
+export function decodeRegistryPageNodes(data: Buffer): PublicKey[] {
+  const offset = 8 + 1 + 4 + 2; // discriminator, bump, index, count
+  const nodes: PublicKey[] = [];
+  for (let i = 0; i < 16; i++) {
+    const slot = new PublicKey(data.slice(offset + i * 32, offset + (i + 1) * 32));
+    if (!slot.equals(PublicKey.default)) {
+      nodes.push(slot);
+    }
+  }
+  return nodes;
+}
+
+export interface ClaimBonusAccounts {
   node: PublicKey;
-  token_vault: PublicKey;
+  bonus_epoch: PublicKey;
+  bonus_claim: PublicKey;
+  token_vault: PublicKey; // TokenVault of the $SCRAPE mint
+  scrape_mint: PublicKey;
   vault_token_account: PublicKey;
   node_token_account: PublicKey;
   signer: PublicKey;
+  config: PublicKey;
+  reputation_config: PublicKey;
   token_program: PublicKey;
 }
 
-export function createClaimTaskRewardsInstruction(
-  accounts: ClaimTaskRewardsAccounts,
+export interface ClaimBonusArgs {
+  epoch: BN;
+}
+
+export function createClaimBonusInstruction(
+  accounts: ClaimBonusAccounts,
+  args: ClaimBonusArgs,
   programId: PublicKey = PROGRAM_ID
 ): TransactionInstruction {
   const keys = [
     { pubkey: accounts.node, isSigner: false, isWritable: true },
+    { pubkey: accounts.bonus_epoch, isSigner: false, isWritable: true },
+    { pubkey: accounts.bonus_claim, isSigner: false, isWritable: true },
     { pubkey: accounts.token_vault, isSigner: false, isWritable: true },
+    { pubkey: accounts.scrape_mint, isSigner: false, isWritable: false },
     { pubkey: accounts.vault_token_account, isSigner: false, isWritable: true },
     { pubkey: accounts.node_token_account, isSigner: false, isWritable: true },
     { pubkey: accounts.signer, isSigner: true, isWritable: false },
+    { pubkey: accounts.config, isSigner: false, isWritable: false },
+    { pubkey: accounts.reputation_config, isSigner: false, isWritable: false },
     { pubkey: accounts.token_program, isSigner: false, isWritable: false },
   ];
 
-  const discriminator = Buffer.from([242, 238, 29, 42, 69, 54, 107, 45]);
-  const data = discriminator;
+  const discriminator = Buffer.from([143, 250, 0, 123, 176, 198, 110, 71]);
+  const data = Buffer.concat([discriminator, args.epoch.toArrayLike(Buffer, "le", 8)]);
 
   return new TransactionInstruction({
     keys,
@@ -256,16 +550,21 @@ export interface CompleteTaskAccounts {
   task: PublicKey;
   node: PublicKey;
   token_vault: PublicKey;
-  vault_token_account: PublicKey;
-  node_token_account: PublicKey;
   signer: PublicKey;
-  token_program: PublicKey;
 }
 
+export type StorageKind = "ipfs" | "arweave" | "https";
+
 export interface CompleteTaskArgs {
-  ipfs_hash: string;
+  size_bytes: BN;
+  record_count: BN;
+  content_hash: Buffer; // SHA-256, 32 bytes
+  storage: StorageKind;
+  uri: string;
 }
 
+const STORAGE_KIND_INDEX: Record<StorageKind, number> = { ipfs: 0, arweave: 1, https: 2 };
+
 export function createCompleteTaskInstruction(
   accounts: CompleteTaskAccounts,
   args: CompleteTaskArgs,
@@ -275,20 +574,27 @@ export function createCompleteTaskInstruction(
     { pubkey: accounts.task, isSigner: false, isWritable: true },
     { pubkey: accounts.node, isSigner: false, isWritable: true },
     { pubkey: accounts.token_vault, isSigner: false, isWritable: true },
-    { pubkey: accounts.vault_token_account, isSigner: false, isWritable: true },
-    { pubkey: accounts.node_token_account, isSigner: false, isWritable: true },
     { pubkey: accounts.signer, isSigner: true, isWritable: false },
-    { pubkey: accounts.token_program, isSigner: false, isWritable: false },
   ];
 
+  if (args.content_hash.length !== 32) {
+    throw new Error("content_hash must be a 32-byte SHA-256 digest");
+  }
+
   const discriminator = Buffer.from([109, 167, 192, 41, 129, 108, 220, 196]);
   const buffers = [discriminator];
 
-  const ipfs_hashBytes = Buffer.from(args.ipfs_hash, 'utf8');
-  const ipfs_hashLenBuffer = Buffer.alloc(4);
-  ipfs_hashLenBuffer.writeUInt32LE(ipfs_hashBytes.length, 0);
-  buffers.push(ipfs_hashLenBuffer);
-  buffers.push(ipfs_hashBytes);
+  // Serialize DatasetManifest
+  buffers.push(args.size_bytes.toArrayLike(Buffer, "le", 8));
+  buffers.push(args.record_count.toArrayLike(Buffer, "le", 8));
+  buffers.push(args.content_hash);
+  buffers.push(Buffer.from([STORAGE_KIND_INDEX[args.storage]]));
+
+  const uriBytes = Buffer.from(args.uri, 'utf8');
+  const uriLenBuffer = Buffer.alloc(4);
+  uriLenBuffer.writeUInt32LE(uriBytes.length, 0);
+  buffers.push(uriLenBuffer);
+  buffers.push(uriBytes);
 
   const data = Buffer.concat(buffers);
 
@@ -334,8 +640,13 @@ export interface CreateEndpointNodeAccounts {
   rent: PublicKey;
 }
 
+export interface CreateEndpointNodeArgs {
+  encryption_key: Buffer; // x25519 public key providers seal their connection info to
+}
+
 export function createCreateEndpointNodeInstruction(
   accounts: CreateEndpointNodeAccounts,
+  args: CreateEndpointNodeArgs,
   programId: PublicKey = PROGRAM_ID
 ): TransactionInstruction {
   const keys = [
@@ -345,8 +656,12 @@ export function createCreateEndpointNodeInstruction(
     { pubkey: accounts.rent, isSigner: false, isWritable: false },
   ];
 
+  if (args.encryption_key.length !== 32) {
+    throw new Error("encryption_key must be a 32-byte x25519 public key");
+  }
+
   const discriminator = Buffer.from([139, 201, 153, 100, 196, 112, 229, 52]);
-  const data = discriminator;
+  const data = Buffer.concat([discriminator, args.encryption_key]);
 
   return new TransactionInstruction({
     keys,
@@ -365,9 +680,7 @@ export interface CreateNodeAccounts {
 }
 
 export interface CreateNodeArgs {
-  ipv4: number[];
-  proxy_port: number;
-  client_port: number;
+  connection_commitment: Buffer; // See computeConnectionCommitment
   bandwidth_limit: BN;
 }
 
@@ -388,19 +701,11 @@ export function createCreateNodeInstruction(
   const discriminator = Buffer.from([20, 183, 134, 233, 51, 51, 115, 83]);
   const buffers = [discriminator];
 
-  // Serialize ipv4 (array of 4 u8)
-  const ipv4Buffer = Buffer.from(args.ipv4.map(num => Math.max(0, Math.min(255, num))));
-  buffers.push(ipv4Buffer);
-
-  // Serialize proxy_port (u16)
-  const proxy_portBuffer = Buffer.alloc(2);
-  proxy_portBuffer.writeUInt16LE(args.proxy_port, 0);
-  buffers.push(proxy_portBuffer);
-
-  // Serialize client_port (u16)
-  const client_portBuffer = Buffer.alloc(2);
-  client_portBuffer.writeUInt16LE(args.client_port, 0);
-  buffers.push(client_portBuffer);
+  // Serialize connection_commitment ([u8; 32])
+  if (args.connection_commitment.length !== 32) {
+    throw new Error("connection_commitment must be 32 bytes");
+  }
+  buffers.push(args.connection_commitment);
 
   // Serialize bandwidth_limit (u64)
   const bandwidth_limitBuffer = Buffer.alloc(8);
@@ -421,7 +726,8 @@ export interface CreateTaskAccounts {
   task: PublicKey;
   client: PublicKey;
   endpoint_node: PublicKey;
-  token_vault: PublicKey;
+  token_vault: PublicKey; // TokenVault of `payment_mint`
+  payment_mint: PublicKey; // Must be approved by governance
   vault_token_account: PublicKey;
   user_token_account: PublicKey;
   system_program: PublicKey;
@@ -429,12 +735,39 @@ export interface CreateTaskAccounts {
   rent: PublicKey;
 }
 
+export type AsnClass = "unknown" | "residential" | "mobile" | "datacenter";
+
+const ASN_CLASS_INDEX: Record<AsnClass, number> = { unknown: 0, residential: 1, mobile: 2, datacenter: 3 };
+
+// Node capabilities a task needs; unset fields accept any node
+export interface TaskRequirements {
+  country: string | null; // ISO 3166-1 alpha-2, e.g. "DE"
+  region: string | null; // ISO 3166-2 subdivision within `country`, e.g. "BY"
+  asn_class: AsnClass | null;
+  ipv6: boolean;
+  js_rendering: boolean;
+  formats: number; // output_format flags: JSONL = 1, JSON = 2, CSV = 4, PARQUET = 8
+}
+
 export interface CreateTaskArgs {
   url: string;
   filter: string;
   label: string;
   format: string;
   reward: BN;
+  deadline: BN; // Unix timestamp after which an unassigned task can be refunded
+  allow_self_claim: boolean;
+  requirements: TaskRequirements;
+}
+
+// Borsh-serializes an Option<[u8; N]> from an ASCII code, zero-padded to `len`
+function serializeCode(code: string | null, len: number): Buffer {
+  if (code === null) {
+    return Buffer.from([0]);
+  }
+  const bytes = Buffer.alloc(len);
+  Buffer.from(code, "ascii").copy(bytes, 0, 0, len);
+  return Buffer.concat([Buffer.from([1]), bytes]);
 }
 
 export function createCreateTaskInstruction(
@@ -448,6 +781,7 @@ export function createCreateTaskInstruction(
     { pubkey: accounts.client, isSigner: false, isWritable: true },
     { pubkey: accounts.endpoint_node, isSigner: false, isWritable: false },
     { pubkey: accounts.token_vault, isSigner: false, isWritable: true },
+    { pubkey: accounts.payment_mint, isSigner: false, isWritable: false },
     { pubkey: accounts.vault_token_account, isSigner: false, isWritable: true },
     { pubkey: accounts.user_token_account, isSigner: false, isWritable: true },
     { pubkey: accounts.system_program, isSigner: false, isWritable: false },
@@ -487,9 +821,24 @@ export function createCreateTaskInstruction(
   buffers.push(formatBytes);
 
   // Serialize reward (u64)
-  const rewardBuffer = Buffer.alloc(8);
-  args.reward.toBuffer().copy(rewardBuffer);
-  buffers.push(rewardBuffer);
+  buffers.push(args.reward.toArrayLike(Buffer, "le", 8));
+
+  // Serialize deadline (i64)
+  buffers.push(args.deadline.toTwos(64).toArrayLike(Buffer, "le", 8));
+
+  // Serialize allow_self_claim (bool)
+  buffers.push(Buffer.from([args.allow_self_claim ? 1 : 0]));
+
+  // Serialize requirements (TaskRequirements)
+  const requirements = args.requirements;
+  buffers.push(serializeCode(requirements.country, 2));
+  buffers.push(serializeCode(requirements.region, 3));
+  buffers.push(
+    requirements.asn_class === null
+      ? Buffer.from([0])
+      : Buffer.from([1, ASN_CLASS_INDEX[requirements.asn_class]])
+  );
+  buffers.push(Buffer.from([requirements.ipv6 ? 1 : 0, requirements.js_rendering ? 1 : 0, requirements.formats]));
 
   const data = Buffer.concat(buffers);
 
@@ -503,20 +852,75 @@ export function createCreateTaskInstruction(
 export interface DownloadDatasetAccounts {
   task: PublicKey;
   client: PublicKey;
+  dataset_access: PublicKey | null; // Required unless the signer owns the task
+  download_receipt: PublicKey; // findDownloadReceiptPda(task, signer)
+  fee_receiver: PublicKey; // Config fee receiver, paid when paying in SOL
+  token_vault: PublicKey; // TokenVault of the $SCRAPE mint
+  scrape_mint: PublicKey | null; // Required when paying in $SCRAPE
+  vault_token_account: PublicKey | null; // Required when paying in $SCRAPE
+  user_token_account: PublicKey | null; // Required when paying in $SCRAPE
   signer: PublicKey;
+  config: PublicKey;
+  token_program: PublicKey;
+}
+
+export type PaymentMethod = "sol" | "scrape";
+
+const PAYMENT_METHOD_INDEX: Record<PaymentMethod, number> = { sol: 0, scrape: 1 };
+
+export interface DownloadDatasetArgs {
+  payment: PaymentMethod;
 }
 
 export function createDownloadDatasetInstruction(
   accounts: DownloadDatasetAccounts,
+  args: DownloadDatasetArgs,
   programId: PublicKey = PROGRAM_ID
 ): TransactionInstruction {
+  // Anchor treats the program ID as "not provided" for optional accounts
   const keys = [
     { pubkey: accounts.task, isSigner: false, isWritable: false },
-    { pubkey: accounts.client, isSigner: false, isWritable: false },
-    { pubkey: accounts.signer, isSigner: true, isWritable: false },
+    { pubkey: accounts.client, isSigner: false, isWritable: true },
+    { pubkey: accounts.dataset_access ?? programId, isSigner: false, isWritable: false },
+    { pubkey: accounts.download_receipt, isSigner: false, isWritable: true },
+    { pubkey: accounts.fee_receiver, isSigner: false, isWritable: true },
+    { pubkey: accounts.token_vault, isSigner: false, isWritable: true },
+    { pubkey: accounts.scrape_mint ?? programId, isSigner: false, isWritable: false },
+    { pubkey: accounts.vault_token_account ?? programId, isSigner: false, isWritable: !!accounts.vault_token_account },
+    { pubkey: accounts.user_token_account ?? programId, isSigner: false, isWritable: !!accounts.user_token_account },
+    { pubkey: accounts.signer, isSigner: true, isWritable: true },
+    { pubkey: accounts.config, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: accounts.token_program, isSigner: false, isWritable: false },
   ];
 
   const discriminator = Buffer.from([145, 106, 229, 180, 207, 34, 11, 81]);
+  const data = Buffer.concat([discriminator, Buffer.from([PAYMENT_METHOD_INDEX[args.payment]])]);
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data,
+  });
+}
+
+export interface ReconcileVaultAccounts {
+  token_vault: PublicKey;
+  vault_token_account: PublicKey;
+  signer: PublicKey;
+}
+
+export function createReconcileVaultInstruction(
+  accounts: ReconcileVaultAccounts,
+  programId: PublicKey = PROGRAM_ID
+): TransactionInstruction {
+  const keys = [
+    { pubkey: accounts.token_vault, isSigner: false, isWritable: true },
+    { pubkey: accounts.vault_token_account, isSigner: false, isWritable: false },
+    { pubkey: accounts.signer, isSigner: true, isWritable: false },
+  ];
+
+  const discriminator = Buffer.from([155, 3, 193, 142, 152, 183, 87, 196]);
   const data = discriminator;
 
   return new TransactionInstruction({
@@ -527,27 +931,41 @@ export function createDownloadDatasetInstruction(
 }
 
 export interface InitTokenVaultAccounts {
-  signer: PublicKey;
-  token_vault: PublicKey;
+  admin: PublicKey;
+  token_vault: PublicKey; // findTokenVaultPda(mint)
+  mint: PublicKey;
   vault_token_account: PublicKey;
+  config: PublicKey;
   system_program: PublicKey;
   rent: PublicKey;
 }
 
+export interface InitTokenVaultArgs {
+  owner: PublicKey; // Treasury authority of the vault
+  rate_per_mb: BN;
+}
+
 export function createInitTokenVaultInstruction(
   accounts: InitTokenVaultAccounts,
+  args: InitTokenVaultArgs,
   programId: PublicKey = PROGRAM_ID
 ): TransactionInstruction {
   const keys = [
-    { pubkey: accounts.signer, isSigner: true, isWritable: true },
+    { pubkey: accounts.admin, isSigner: true, isWritable: true },
     { pubkey: accounts.token_vault, isSigner: false, isWritable: true },
+    { pubkey: accounts.mint, isSigner: false, isWritable: false },
     { pubkey: accounts.vault_token_account, isSigner: false, isWritable: false },
+    { pubkey: accounts.config, isSigner: false, isWritable: false },
     { pubkey: accounts.system_program, isSigner: false, isWritable: false },
     { pubkey: accounts.rent, isSigner: false, isWritable: false },
   ];
 
   const discriminator = Buffer.from([203, 26, 194, 169, 252, 226, 179, 180]);
-  const data = discriminator;
+  const data = Buffer.concat([
+    discriminator,
+    args.owner.toBuffer(),
+    args.rate_per_mb.toArrayLike(Buffer, "le", 8),
+  ]);
 
   return new TransactionInstruction({
     keys,
@@ -585,6 +1003,7 @@ export function createNodeRegistryInitializeInstruction(
 export interface PreviewDatasetAccounts {
   task: PublicKey;
   client: PublicKey;
+  dataset_access: PublicKey | null; // Required unless the signer owns the task
   signer: PublicKey;
 }
 
@@ -595,6 +1014,8 @@ export function createPreviewDatasetInstruction(
   const keys = [
     { pubkey: accounts.task, isSigner: false, isWritable: false },
     { pubkey: accounts.client, isSigner: false, isWritable: false },
+    // Anchor treats the program ID as "not provided" for optional accounts
+    { pubkey: accounts.dataset_access ?? programId, isSigner: false, isWritable: false },
     { pubkey: accounts.signer, isSigner: true, isWritable: false },
   ];
 
@@ -611,6 +1032,7 @@ export function createPreviewDatasetInstruction(
 export interface UpdateClientReportAccounts {
   client: PublicKey;
   signer: PublicKey;
+  config: PublicKey;
 }
 
 export function createUpdateClientReportInstruction(
@@ -620,6 +1042,7 @@ export function createUpdateClientReportInstruction(
   const keys = [
     { pubkey: accounts.client, isSigner: false, isWritable: true },
     { pubkey: accounts.signer, isSigner: true, isWritable: false },
+    { pubkey: accounts.config, isSigner: false, isWritable: false },
   ];
 
   const discriminator = Buffer.from([127, 12, 207, 183, 118, 13, 84, 88]);
@@ -638,9 +1061,7 @@ export interface UpdateNodeAccounts {
 }
 
 export interface UpdateNodeArgs {
-  ipv4: number[];
-  proxy_port: number;
-  client_port: number;
+  connection_commitment: Buffer; // computeConnectionCommitment(info, salt)
   bandwidth_limit: BN;
 }
 
@@ -657,24 +1078,14 @@ export function createUpdateNodeInstruction(
   const discriminator = Buffer.from([13, 65, 246, 102, 101, 91, 98, 43]);
   const buffers = [discriminator];
 
-  // Serialize ipv4 (array of 4 u8)
-  const ipv4Buffer = Buffer.from(args.ipv4.map(num => Math.max(0, Math.min(255, num))));
-  buffers.push(ipv4Buffer);
-
-  // Serialize proxy_port (u16)
-  const proxy_portBuffer = Buffer.alloc(2);
-  proxy_portBuffer.writeUInt16LE(args.proxy_port, 0);
-  buffers.push(proxy_portBuffer);
-
-  // Serialize client_port (u16)
-  const client_portBuffer = Buffer.alloc(2);
-  client_portBuffer.writeUInt16LE(args.client_port, 0);
-  buffers.push(client_portBuffer);
+  // Serialize connection_commitment ([u8; 32])
+  if (args.connection_commitment.length !== 32) {
+    throw new Error("connection_commitment must be 32 bytes");
+  }
+  buffers.push(args.connection_commitment);
 
   // Serialize bandwidth_limit (u64)
-  const bandwidth_limitBuffer = Buffer.alloc(8);
-  args.bandwidth_limit.toBuffer().copy(bandwidth_limitBuffer);
-  buffers.push(bandwidth_limitBuffer);
+  buffers.push(args.bandwidth_limit.toArrayLike(Buffer, "le", 8));
 
   const data = Buffer.concat(buffers);
 
@@ -692,7 +1103,6 @@ export interface UpdateNodeReportAccounts {
 
 export interface UpdateNodeReportArgs {
   bandwidth_used: BN;
-  reputation_increase: BN;
 }
 
 export function createUpdateNodeReportInstruction(
@@ -713,11 +1123,6 @@ export function createUpdateNodeReportInstruction(
   args.bandwidth_used.toBuffer().copy(bandwidth_usedBuffer);
   buffers.push(bandwidth_usedBuffer);
 
-  // Serialize reputation_increase (u64)
-  const reputation_increaseBuffer = Buffer.alloc(8);
-  args.reputation_increase.toBuffer().copy(reputation_increaseBuffer);
-  buffers.push(reputation_increaseBuffer);
-
   const data = Buffer.concat(buffers);
 
   return new TransactionInstruction({
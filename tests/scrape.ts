@@ -1,16 +1,198 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { Program, AnchorError, BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { createHash } from "crypto";
+import { expect } from "chai";
 import { Scrape } from "../target/types/scrape";
 
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
 describe("scrape", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.scrape as Program<Scrape>;
+  const connection = provider.connection;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const configPda = pda(Buffer.from("CONFIG"));
+  const registryPda = pda(Buffer.from("NODE_REGISTRY"));
+  const clientPda = pda(Buffer.from("CLIENT"), admin.publicKey.toBuffer());
+  const endpointNodePda = pda(Buffer.from("ENDPOINT_NODE"), admin.publicKey.toBuffer());
+  const [programDataPda] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  );
+
+  const providerOwner = Keypair.generate();
+  const providerNodePda = pda(Buffer.from("PROVIDER_NODE"), providerOwner.publicKey.toBuffer());
+  const stakePda = pda(Buffer.from("NODE_STAKE"), providerNodePda.toBuffer());
+
+  let mint: PublicKey;
+  let tokenVaultPda: PublicKey;
+  let vaultTokenAccount: PublicKey;
+  let taskPda: PublicKey;
+
+  before(async () => {
+    await connection.confirmTransaction(
+      await connection.requestAirdrop(providerOwner.publicKey, 10 * LAMPORTS_PER_SOL)
+    );
+
+    // $SCRAPE mint and the vault's token account
+    mint = await createMint(connection, admin, admin.publicKey, null, 6);
+    tokenVaultPda = pda(Buffer.from("TOKEN_VAULT"), mint.toBuffer());
+    vaultTokenAccount = (await getOrCreateAssociatedTokenAccount(connection, admin, mint, tokenVaultPda, true)).address;
+
+    await program.methods
+      .initConfig({
+        scrapeMint: mint,
+        bonusEpochLength: new BN(86_400),
+        reputationThreshold: new BN(1),
+        freeThreshold: new BN(100),
+        ratePerMbLamports: new BN(1_000),
+        ratePerMbTokens: new BN(1_000),
+        allowancePeriod: new BN(86_400),
+        feeReceiver: admin.publicKey,
+        protocolFeeBps: 0,
+        marketProviderBps: 5_000,
+        assignmentTimeout: new BN(3_600),
+        reviewWindow: new BN(3_600),
+        unstakeCooldown: new BN(7_200),
+        minStake: new BN(1_000),
+        stallSlashBps: 100,
+        disputeSlashBps: 1_000,
+        randomnessFeed: null,
+        taskFeeBps: 0,
+        disputeTimeout: new BN(7 * 86_400),
+      })
+      .accountsPartial({ config: configPda, signer: admin.publicKey, programData: programDataPda })
+      .rpc();
+
+    await program.methods
+      .initTokenVault(admin.publicKey, new BN(1_000))
+      .accountsPartial({ admin: admin.publicKey, tokenVault: tokenVaultPda, mint, vaultTokenAccount, config: configPda })
+      .rpc();
+
+    await program.methods
+      .nodeRegistryInitialize()
+      .accountsPartial({ nodeRegistry: registryPda, signer: admin.publicKey })
+      .rpc();
+
+    // The wallet acts as both the client and its endpoint node
+    await program.methods.createClient().accountsPartial({ signer: admin.publicKey, client: clientPda }).rpc();
+    await program.methods
+      .createEndpointNode(Array.from(Keypair.generate().publicKey.toBytes()))
+      .accountsPartial({ signer: admin.publicKey, endpointNode: endpointNodePda })
+      .rpc();
+
+    // A staked provider node that can take any task
+    const nodeTokenAccount = await createAccount(connection, admin, mint, providerOwner.publicKey);
+    await mintTo(connection, admin, mint, nodeTokenAccount, admin, 1_000);
+    const connectionCommitment = Array.from(createHash("sha256").update("connection").digest());
+    await program.methods
+      .createNode(
+        connectionCommitment,
+        new BN(1_000),
+        new BN(1_000),
+        {
+          country: Array.from(Buffer.from("DE")),
+          region: [0, 0, 0],
+          asnClass: { residential: {} },
+          ipv6: false,
+          jsRendering: false,
+          maxConcurrentTasks: 4,
+          formats: 1,
+        },
+        0
+      )
+      .accountsPartial({
+        signer: providerOwner.publicKey,
+        providerNode: providerNodePda,
+        nodeRegistry: registryPda,
+        registryPage: pda(Buffer.from("NODE_REGISTRY_PAGE"), Buffer.from([0, 0, 0, 0])),
+        nodeTokenAccount,
+        stakeTokenAccount: stakePda,
+        tokenVault: tokenVaultPda,
+        scrapeMint: mint,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([providerOwner])
+      .rpc();
+
+    // A task paid in $SCRAPE that the node claims for itself
+    const userTokenAccount = await createAccount(connection, admin, mint, admin.publicKey);
+    await mintTo(connection, admin, mint, userTokenAccount, admin, 1_000_000);
+    taskPda = pda(Buffer.from("TASK"), admin.publicKey.toBuffer(), new BN(0).toArrayLike(Buffer, "le", 8));
+    await program.methods
+      .createTask(
+        "https://example.com",
+        "",
+        "",
+        "JSONL",
+        new BN(1_000_000),
+        new BN(Math.floor(Date.now() / 1000) + 3_600),
+        true,
+        { country: null, region: null, asnClass: null, ipv6: false, jsRendering: false, formats: 1 }
+      )
+      .accountsPartial({
+        signer: admin.publicKey,
+        task: taskPda,
+        client: clientPda,
+        endpointNode: endpointNodePda,
+        tokenVault: tokenVaultPda,
+        paymentMint: mint,
+        vaultTokenAccount,
+        userTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    await program.methods
+      .assignTask()
+      .accountsPartial({
+        task: taskPda,
+        node: providerNodePda,
+        endpointNode: endpointNodePda,
+        signer: providerOwner.publicKey,
+        config: configPda,
+      })
+      .signers([providerOwner])
+      .rpc();
+  });
+
+  it("rejects complete_task from a signer that does not operate the assigned node", async () => {
+    const stranger = Keypair.generate();
+
+    try {
+      await program.methods
+        .completeTask({
+          sizeBytes: new BN(1_000),
+          recordCount: new BN(10),
+          contentHash: Array.from(createHash("sha256").update("dataset").digest()),
+          storage: { https: {} },
+          uri: "https://example.com/dataset.jsonl",
+        })
+        .accountsPartial({ task: taskPda, node: providerNodePda, tokenVault: tokenVaultPda, signer: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      expect.fail("complete_task should have been rejected");
+    } catch (err) {
+      expect(err).to.be.instanceOf(AnchorError);
+      expect((err as AnchorError).error.errorCode.code).to.equal("UnauthorizedNode");
+    }
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+    const task = await program.account.task.fetch(taskPda);
+    expect(task.status).to.deep.equal({ assigned: {} });
   });
 });